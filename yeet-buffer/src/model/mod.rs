@@ -16,6 +16,7 @@ pub enum Mode {
     #[default]
     Navigation,
     Normal,
+    Visual,
 }
 
 impl Mode {
@@ -31,6 +32,7 @@ impl Display for Mode {
             Mode::Insert => "insert".to_string(),
             Mode::Navigation => "navigation".to_string(),
             Mode::Normal => "normal".to_string(),
+            Mode::Visual => "visual".to_string(),
         };
 
         write!(f, "{}", content)
@@ -40,6 +42,7 @@ impl Display for Mode {
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum CommandMode {
     Command,
+    Find,
     PrintMultiline,
     Search(SearchDirection),
 }
@@ -73,6 +76,18 @@ pub struct Cursor {
     pub hide_cursor_line: bool,
     pub horizontal_index: CursorPosition,
     pub vertical_index: usize,
+    pub visual_start: Option<usize>,
+}
+
+impl Cursor {
+    pub fn get_visual_range(&self) -> Option<(usize, usize)> {
+        let start = self.visual_start?;
+        if start <= self.vertical_index {
+            Some((start, self.vertical_index))
+        } else {
+            Some((self.vertical_index, start))
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -97,6 +112,7 @@ pub struct BufferLine {
     pub content: Ansi,
     pub search_char_position: Option<Vec<(usize, usize)>>,
     pub signs: Vec<Sign>,
+    pub suffix: Option<String>,
 }
 
 impl BufferLine {
@@ -1,6 +1,6 @@
 use std::time;
 
-use super::{ansi::Ansi, Mode};
+use super::{ansi::Ansi, BufferLine, Mode};
 
 struct Transaction {
     changes: Vec<BufferChanged>,
@@ -11,7 +11,8 @@ struct Transaction {
 pub enum BufferChanged {
     Content(usize, Ansi, Ansi),
     LineAdded(usize, Ansi),
-    LineRemoved(usize, Ansi),
+    // NOTE: removed lines keep signs and suffix to restore them on undo
+    LineRemoved(usize, BufferLine),
 }
 
 #[derive(Default)]
@@ -55,23 +56,13 @@ impl Undo {
     }
 
     pub fn get_uncommited_changes(&self) -> Vec<BufferChanged> {
-        if self.transactions.is_empty() {
+        let start = get_position(&self.current_save_index);
+        let end = get_position(&self.current_transaction_index);
+        if end <= start {
             return Vec::new();
         }
 
-        let start = if let Some(index) = self.current_save_index {
-            index + 1
-        } else {
-            0
-        };
-
-        let end = if let Some(index) = self.current_transaction_index {
-            index
-        } else {
-            self.transactions.len() - 1
-        };
-
-        self.transactions[start..end + 1]
+        self.transactions[start..end]
             .iter()
             .fold(Vec::new(), |mut acc, t| {
                 acc.extend(t.changes.clone());
@@ -79,24 +70,43 @@ impl Undo {
             })
     }
 
-    pub fn save(&mut self) -> Vec<BufferChanged> {
+    pub fn redo(&mut self) -> Option<Vec<BufferChanged>> {
         self.close_transaction();
 
-        if self.transactions.is_empty() {
-            return Vec::new();
-        }
+        let next = get_position(&self.current_transaction_index);
+        let transaction = self.transactions.get(next)?;
+
+        self.current_transaction_index = Some(next);
+
+        Some(transaction.changes.clone())
+    }
+
+    pub fn save(&mut self) -> Vec<BufferChanged> {
+        self.close_transaction();
 
         let changes = self.get_uncommited_changes();
 
-        self.current_save_index = if let Some(index) = self.current_transaction_index {
-            Some(index)
-        } else {
-            Some(self.transactions.len() - 1)
-        };
+        self.transactions
+            .truncate(get_position(&self.current_transaction_index));
+        self.current_save_index = self.current_transaction_index;
 
         changes
     }
 
+    pub fn undo(&mut self) -> Option<Vec<BufferChanged>> {
+        self.close_transaction();
+
+        // NOTE: saved transactions are already persisted, thus undo stops at the save point
+        if self.current_transaction_index == self.current_save_index {
+            return None;
+        }
+
+        let index = self.current_transaction_index?;
+        self.current_transaction_index = index.checked_sub(1);
+
+        self.transactions.get(index).map(|t| t.changes.clone())
+    }
+
     fn add_transaction(&mut self, changes: Vec<BufferChanged>) {
         if changes.is_empty() {
             return;
@@ -107,6 +117,9 @@ impl Undo {
             Err(_) => 0,
         };
 
+        self.transactions
+            .truncate(get_position(&self.current_transaction_index));
+
         self.transactions.push(Transaction {
             changes,
             _timestamp: timestamp,
@@ -116,6 +129,13 @@ impl Undo {
     }
 }
 
+fn get_position(index: &Option<usize>) -> usize {
+    match index {
+        Some(index) => index + 1,
+        None => 0,
+    }
+}
+
 fn update(
     current: &Option<BufferChanged>,
     new: &BufferChanged,
@@ -259,6 +279,7 @@ mod test {
     fn get_uncommited_changes() {
         use crate::model::ansi::Ansi;
         use crate::model::undo::BufferChanged;
+        use crate::model::BufferLine;
 
         let mut undo = super::Undo::default();
         let changes = undo.save();
@@ -269,7 +290,7 @@ mod test {
             &crate::model::Mode::Insert,
             vec![
                 BufferChanged::LineAdded(0, Ansi::new("a")),
-                BufferChanged::LineRemoved(4, Ansi::new("m")),
+                BufferChanged::LineRemoved(4, BufferLine::from("m")),
             ],
         );
 
@@ -282,7 +303,7 @@ mod test {
             changes,
             vec![
                 BufferChanged::LineAdded(0, Ansi::new("a")),
-                BufferChanged::LineRemoved(4, Ansi::new("m")),
+                BufferChanged::LineRemoved(4, BufferLine::from("m")),
             ]
         );
 
@@ -295,23 +316,23 @@ mod test {
             changes,
             vec![
                 BufferChanged::LineAdded(0, Ansi::new("a")),
-                BufferChanged::LineRemoved(4, Ansi::new("m")),
+                BufferChanged::LineRemoved(4, BufferLine::from("m")),
                 BufferChanged::LineAdded(2, Ansi::new("h")),
             ]
         );
 
         undo.add(
             &crate::model::Mode::Insert,
-            vec![BufferChanged::LineRemoved(5, Ansi::new("m"))],
+            vec![BufferChanged::LineRemoved(5, BufferLine::from("m"))],
         );
         let changes = undo.save();
         assert_eq!(
             changes,
             vec![
                 BufferChanged::LineAdded(0, Ansi::new("a")),
-                BufferChanged::LineRemoved(4, Ansi::new("m")),
+                BufferChanged::LineRemoved(4, BufferLine::from("m")),
                 BufferChanged::LineAdded(2, Ansi::new("h")),
-                BufferChanged::LineRemoved(5, Ansi::new("m")),
+                BufferChanged::LineRemoved(5, BufferLine::from("m")),
             ]
         );
 
@@ -329,26 +350,96 @@ mod test {
         assert_eq!(changes, vec![]);
     }
 
+    #[test]
+    fn undo_and_redo() {
+        use crate::model::ansi::Ansi;
+        use crate::model::undo::BufferChanged;
+        use crate::model::BufferLine;
+
+        let mut undo = super::Undo::default();
+        assert_eq!(undo.undo(), None);
+        assert_eq!(undo.redo(), None);
+
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![BufferChanged::LineRemoved(0, BufferLine::from("a"))],
+        );
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![BufferChanged::Content(1, Ansi::new("b"), Ansi::new("c"))],
+        );
+
+        let changes = undo.undo();
+        assert_eq!(
+            changes,
+            Some(vec![BufferChanged::Content(
+                1,
+                Ansi::new("b"),
+                Ansi::new("c")
+            )])
+        );
+        assert_eq!(
+            undo.get_uncommited_changes(),
+            vec![BufferChanged::LineRemoved(0, BufferLine::from("a"))]
+        );
+
+        let changes = undo.undo();
+        assert_eq!(
+            changes,
+            Some(vec![BufferChanged::LineRemoved(0, BufferLine::from("a"))])
+        );
+        assert_eq!(undo.get_uncommited_changes(), vec![]);
+        assert_eq!(undo.undo(), None);
+
+        let changes = undo.redo();
+        assert_eq!(
+            changes,
+            Some(vec![BufferChanged::LineRemoved(0, BufferLine::from("a"))])
+        );
+        assert_eq!(
+            undo.get_uncommited_changes(),
+            vec![BufferChanged::LineRemoved(0, BufferLine::from("a"))]
+        );
+
+        undo.add(
+            &crate::model::Mode::Normal,
+            vec![BufferChanged::LineAdded(0, Ansi::new("d"))],
+        );
+        assert_eq!(undo.redo(), None);
+        assert_eq!(
+            undo.get_uncommited_changes(),
+            vec![
+                BufferChanged::LineRemoved(0, BufferLine::from("a")),
+                BufferChanged::LineAdded(0, Ansi::new("d")),
+            ]
+        );
+
+        let changes = undo.save();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(undo.undo(), None);
+    }
+
     #[test]
     fn consolidate() {
         use crate::model::ansi::Ansi;
         use crate::model::undo::BufferChanged;
+        use crate::model::BufferLine;
 
         let changes = vec![
             BufferChanged::LineAdded(0, Ansi::new("a")),
             BufferChanged::Content(0, Ansi::new("a"), Ansi::new("d")),
-            BufferChanged::LineRemoved(0, Ansi::new("d")),
+            BufferChanged::LineRemoved(0, BufferLine::from("d")),
             BufferChanged::LineAdded(0, Ansi::new("e")),
             BufferChanged::LineAdded(0, Ansi::new("f")),
-            BufferChanged::LineRemoved(0, Ansi::new("e")),
+            BufferChanged::LineRemoved(0, BufferLine::from("e")),
             BufferChanged::LineAdded(1, Ansi::new("l")),
             BufferChanged::LineAdded(2, Ansi::new("g")),
             BufferChanged::Content(2, Ansi::new(""), Ansi::new("h")),
             BufferChanged::Content(3, Ansi::new("i_old"), Ansi::new("i")),
             BufferChanged::LineAdded(3, Ansi::new("j")),
-            BufferChanged::LineRemoved(3, Ansi::new("j")),
+            BufferChanged::LineRemoved(3, BufferLine::from("j")),
             BufferChanged::Content(3, Ansi::new(""), Ansi::new("k")),
-            BufferChanged::LineRemoved(4, Ansi::new("m")),
+            BufferChanged::LineRemoved(4, BufferLine::from("m")),
         ];
         let consolidated_changes = super::consolidate_modifications(&changes);
 
@@ -359,7 +450,7 @@ mod test {
                 BufferChanged::LineAdded(1, Ansi::new("l")),
                 BufferChanged::LineAdded(2, Ansi::new("h")),
                 BufferChanged::Content(3, Ansi::new("i_old"), Ansi::new("k")),
-                BufferChanged::LineRemoved(4, Ansi::new("m")),
+                BufferChanged::LineRemoved(4, BufferLine::from("m")),
             ]
         );
     }
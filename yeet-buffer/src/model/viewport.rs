@@ -4,6 +4,9 @@ use super::{BufferLine, SignIdentifier};
 
 #[derive(Debug, Default)]
 pub struct WindowSettings {
+    pub line_number: LineNumber,
+    pub line_number_width: usize,
+    pub show_suffix: bool,
     pub sign_column_width: usize,
 }
 
@@ -14,6 +17,7 @@ pub struct ViewPort {
     pub horizontal_index: usize,
     pub line_number: LineNumber,
     pub line_number_width: usize,
+    pub show_suffix: bool,
     pub sign_column_width: usize,
     pub vertical_index: usize,
     pub width: usize,
@@ -54,14 +58,28 @@ impl ViewPort {
             0
         };
 
-        self.get_prefix_width() + self.get_border_width() + custom_prefix_width
+        self.get_prefix_width()
+            + self.get_border_width()
+            + custom_prefix_width
+            + self.get_suffix_width(line)
     }
 
     fn get_prefix_width(&self) -> usize {
         self.sign_column_width + self.get_line_number_width()
     }
 
+    pub fn get_suffix_width(&self, line: &BufferLine) -> usize {
+        match &line.suffix {
+            // NOTE: plus one to separate the suffix from the content
+            Some(suffix) if self.show_suffix => suffix.chars().count() + 1,
+            _ => 0,
+        }
+    }
+
     pub fn set(&mut self, settings: &WindowSettings) {
+        self.line_number = settings.line_number.clone();
+        self.line_number_width = settings.line_number_width;
+        self.show_suffix = settings.show_suffix;
         self.sign_column_width = settings.sign_column_width;
     }
 }
@@ -73,3 +91,26 @@ pub enum LineNumber {
     None,
     Relative,
 }
+
+mod test {
+    #[test]
+    fn get_content_width_with_suffix() {
+        use crate::model::BufferLine;
+
+        let mut viewport = super::ViewPort {
+            sign_column_width: 2,
+            width: 30,
+            ..Default::default()
+        };
+
+        let line = BufferLine {
+            suffix: Some("rwxr-xr-x".to_owned()),
+            ..BufferLine::from("yeet")
+        };
+
+        assert_eq!(viewport.get_content_width(&line), 27);
+
+        viewport.show_suffix = true;
+        assert_eq!(viewport.get_content_width(&line), 17);
+    }
+}
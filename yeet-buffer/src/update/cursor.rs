@@ -279,50 +279,78 @@ fn get_index_correction(mode: &Mode) -> usize {
         Mode::Insert => 0,
         Mode::Navigation => 1,
         Mode::Normal => 1,
+        Mode::Visual => 1,
     }
 }
 
 fn jump_to_next_search(cursor: &mut Cursor, lines: &[BufferLine], direction: &Search) {
-    if cursor.horizontal_index == CursorPosition::None {
-        return;
-    }
+    let horizontal_index = match &cursor.horizontal_index {
+        CursorPosition::Absolute { current, .. } => Some(*current),
+        CursorPosition::End => None,
+        CursorPosition::None => return,
+    };
 
     let vertical_index = cursor.vertical_index;
     let mut enumeration: Vec<_> = lines
         .iter()
         .enumerate()
-        .filter(|(_, bl)| bl.search_char_position.is_some())
+        .filter_map(|(i, bl)| match &bl.search_char_position {
+            Some(it) if !it.is_empty() => Some((i, it)),
+            _ => None,
+        })
         .collect();
 
     enumeration.sort_unstable_by(|(current, _), (cmp, _)| {
         sort_by_index(*current, *cmp, vertical_index, direction)
     });
 
-    for (i, line) in enumeration {
-        let start = match &line.search_char_position {
-            Some(it) => match it.first() {
-                Some(s) => s.0,
-                None => continue,
-            },
-            None => continue,
+    let downward = direction == &Search::Next;
+    let mut target = None;
+    for (i, positions) in &enumeration {
+        let starts = positions.iter().map(|(start, _)| *start);
+        let start = if *i != vertical_index {
+            if downward {
+                starts.min()
+            } else {
+                starts.max()
+            }
+        } else if downward {
+            starts
+                .filter(|start| horizontal_index.is_some_and(|current| start > &current))
+                .min()
+        } else {
+            starts
+                .filter(|start| match horizontal_index {
+                    Some(current) => start < &current,
+                    None => true,
+                })
+                .max()
         };
 
-        let downward = direction == &Search::Next;
-        if i == vertical_index {
-            if let CursorPosition::Absolute { current, .. } = &cursor.horizontal_index {
-                if downward && current >= &start || !downward && current <= &start {
-                    continue;
-                }
-            }
+        if let Some(start) = start {
+            target = Some((*i, start));
+            break;
         }
+    }
+
+    // NOTE: wrapping around the buffer ends on the current line again
+    if target.is_none() {
+        target = enumeration
+            .iter()
+            .find(|(i, _)| *i == vertical_index)
+            .and_then(|(i, positions)| {
+                let starts = positions.iter().map(|(start, _)| *start);
+                let start = if downward { starts.min() } else { starts.max() };
+                start.map(|start| (*i, start))
+            });
+    }
 
+    if let Some((i, start)) = target {
         cursor.vertical_index = i;
         cursor.horizontal_index = CursorPosition::Absolute {
             current: start,
             expanded: start,
         };
-
-        break;
     }
 }
 
@@ -383,6 +411,56 @@ pub fn get_horizontal_index(
 }
 
 mod test {
+    #[test]
+    fn jump_to_next_search_within_line() {
+        use crate::{
+            message::Search,
+            model::{BufferLine, Cursor, CursorPosition},
+        };
+
+        let mut lines = vec![
+            BufferLine::from("foo bar foo"),
+            BufferLine::from("bar"),
+            BufferLine::from("foo"),
+        ];
+        lines[0].search_char_position = Some(vec![(0, 3), (8, 3)]);
+        lines[2].search_char_position = Some(vec![(0, 3)]);
+
+        let mut cursor = Cursor {
+            horizontal_index: CursorPosition::Absolute {
+                current: 0,
+                expanded: 0,
+            },
+            ..Default::default()
+        };
+
+        super::jump_to_next_search(&mut cursor, &lines, &Search::Next);
+        assert_eq!(0, cursor.vertical_index);
+        assert_eq!(
+            CursorPosition::Absolute {
+                current: 8,
+                expanded: 8
+            },
+            cursor.horizontal_index
+        );
+
+        super::jump_to_next_search(&mut cursor, &lines, &Search::Next);
+        assert_eq!(2, cursor.vertical_index);
+
+        super::jump_to_next_search(&mut cursor, &lines, &Search::Next);
+        assert_eq!(0, cursor.vertical_index);
+        assert_eq!(
+            CursorPosition::Absolute {
+                current: 0,
+                expanded: 0
+            },
+            cursor.horizontal_index
+        );
+
+        super::jump_to_next_search(&mut cursor, &lines, &Search::Previous);
+        assert_eq!(2, cursor.vertical_index);
+    }
+
     #[test]
     fn sort_by_index_downward() {
         use crate::message::Search;
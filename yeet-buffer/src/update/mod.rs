@@ -7,6 +7,7 @@ use crate::{
 mod cursor;
 mod find;
 mod modification;
+mod undo;
 mod viewport;
 mod word;
 
@@ -30,6 +31,14 @@ pub fn update_buffer(
                     update_cursor_by_direction(cursor, mode, buffer, &1, &CursorDirection::Left);
                 }
             }
+
+            if let Some(cursor) = cursor {
+                cursor.visual_start = match (from, to) {
+                    (Mode::Visual, Mode::Visual) => cursor.visual_start,
+                    (_, Mode::Visual) => Some(cursor.vertical_index),
+                    (_, _) => None,
+                };
+            }
             Vec::new()
         }
         BufferMessage::Modification(count, modification) => {
@@ -53,6 +62,12 @@ pub fn update_buffer(
             viewport::update_by_direction(viewport, cursor, buffer, direction);
             Vec::new()
         }
+        BufferMessage::Redo => {
+            if let Some(cursor) = cursor {
+                undo::redo(cursor, mode, buffer);
+            }
+            Vec::new()
+        }
         BufferMessage::RemoveLine(index) => {
             buffer.lines.remove(*index);
 
@@ -130,6 +145,12 @@ pub fn update_buffer(
             }
             Vec::new()
         }
+        BufferMessage::Undo => {
+            if let Some(cursor) = cursor {
+                undo::undo(cursor, mode, buffer);
+            }
+            Vec::new()
+        }
         BufferMessage::UpdateViewPortByCursor => Vec::new(),
     };
 
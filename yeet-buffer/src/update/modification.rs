@@ -36,7 +36,7 @@ pub fn update(
                     cursor.vertical_index = line_count - 1;
                 }
 
-                changes.push(BufferChanged::LineRemoved(line_index, line.content));
+                changes.push(BufferChanged::LineRemoved(line_index, line));
             }
 
             cursor::set_outbound_cursor_to_inbound_position(cursor, mode, buffer);
@@ -0,0 +1,114 @@
+use crate::model::{ansi::Ansi, undo::BufferChanged, Buffer, BufferLine, Cursor, Mode};
+
+use super::cursor;
+
+pub fn undo(cursor: &mut Cursor, mode: &Mode, buffer: &mut Buffer) {
+    let changes = match buffer.undo.undo() {
+        Some(it) => it,
+        None => return,
+    };
+
+    for change in changes.iter().rev() {
+        match change {
+            BufferChanged::Content(index, old, _) => set_content(buffer, index, old),
+            BufferChanged::LineAdded(index, _) => remove_line(buffer, index),
+            BufferChanged::LineRemoved(index, line) => insert_line(buffer, index, line.clone()),
+        }
+    }
+
+    set_cursor_to_first_change(cursor, mode, buffer, &changes);
+}
+
+pub fn redo(cursor: &mut Cursor, mode: &Mode, buffer: &mut Buffer) {
+    let changes = match buffer.undo.redo() {
+        Some(it) => it,
+        None => return,
+    };
+
+    for change in changes.iter() {
+        match change {
+            BufferChanged::Content(index, _, new) => set_content(buffer, index, new),
+            BufferChanged::LineAdded(index, content) => insert_line(
+                buffer,
+                index,
+                BufferLine {
+                    content: content.clone(),
+                    ..Default::default()
+                },
+            ),
+            BufferChanged::LineRemoved(index, _) => remove_line(buffer, index),
+        }
+    }
+
+    set_cursor_to_first_change(cursor, mode, buffer, &changes);
+}
+
+fn set_content(buffer: &mut Buffer, index: &usize, content: &Ansi) {
+    if let Some(line) = buffer.lines.get_mut(*index) {
+        line.content = content.clone();
+    }
+}
+
+fn insert_line(buffer: &mut Buffer, index: &usize, line: BufferLine) {
+    if index > &buffer.lines.len() {
+        return;
+    }
+
+    buffer.lines.insert(*index, line);
+}
+
+fn remove_line(buffer: &mut Buffer, index: &usize) {
+    if index < &buffer.lines.len() {
+        buffer.lines.remove(*index);
+    }
+}
+
+fn set_cursor_to_first_change(
+    cursor: &mut Cursor,
+    mode: &Mode,
+    buffer: &Buffer,
+    changes: &[BufferChanged],
+) {
+    let index = changes
+        .iter()
+        .map(|change| match change {
+            BufferChanged::Content(index, _, _)
+            | BufferChanged::LineAdded(index, _)
+            | BufferChanged::LineRemoved(index, _) => *index,
+        })
+        .min();
+
+    if let Some(index) = index {
+        cursor.vertical_index = index;
+    }
+
+    cursor::set_outbound_cursor_to_inbound_position(cursor, mode, buffer);
+}
+
+mod test {
+    #[test]
+    fn undo_restores_removed_line_with_signs_and_suffix() {
+        use crate::model::{undo::BufferChanged, Buffer, BufferLine, Cursor, Mode, Sign};
+
+        let line = BufferLine {
+            signs: vec![Sign {
+                id: "error",
+                content: 'E',
+                priority: 2,
+                style: String::new(),
+            }],
+            suffix: Some("-> target".to_owned()),
+            ..BufferLine::from("link")
+        };
+
+        let mut buffer = Buffer::default();
+        buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::LineRemoved(0, line.clone())],
+        );
+
+        super::undo(&mut Cursor::default(), &Mode::Normal, &mut buffer);
+
+        assert_eq!(vec![line], buffer.lines);
+    }
+}
@@ -10,6 +10,7 @@ pub fn add_line_styles(
     let content_width = vp.get_content_width(line);
     let ansi = line.content.skip_chars(vp.horizontal_index);
     let ansi = add_search_styles(line, &ansi);
+    let ansi = add_visual_styles(vp, mode, cursor, index, content_width, &ansi);
 
     add_cursor_styles(vp, mode, cursor, index, content_width, &ansi)
 }
@@ -17,7 +18,8 @@ pub fn add_line_styles(
 fn add_search_styles(line: &BufferLine, ansi: &Ansi) -> Ansi {
     if let Some(search_char_position) = &line.search_char_position {
         let mut content = ansi.clone();
-        for (index, length) in search_char_position.iter() {
+        // NOTE: reversed to keep the escape sequences of previous matches out of the reset
+        for (index, length) in search_char_position.iter().rev() {
             let reset = format!(
                 "\x1b[0m{}",
                 content.get_ansi_escape_sequences_till_char(*index + 1)
@@ -32,6 +34,46 @@ fn add_search_styles(line: &BufferLine, ansi: &Ansi) -> Ansi {
     }
 }
 
+fn add_visual_styles(
+    vp: &ViewPort,
+    mode: &Mode,
+    cursor: &Option<Cursor>,
+    index: &usize,
+    content_width: usize,
+    ansi: &Ansi,
+) -> Ansi {
+    let mut content = ansi.clone();
+    if mode != &Mode::Visual {
+        return content;
+    }
+
+    let cursor = match cursor {
+        Some(it) => it,
+        None => return content,
+    };
+
+    let (start, end) = match cursor.get_visual_range() {
+        Some(it) => it,
+        None => return content,
+    };
+
+    // NOTE: the cursor line keeps its own highlighting
+    let line_index = index + vp.vertical_index;
+    if line_index == cursor.vertical_index || line_index < start || line_index > end {
+        return content;
+    }
+
+    let char_count = content.count_chars();
+    if content_width > char_count {
+        content.append(" ".repeat(content_width - char_count).as_str());
+    }
+
+    content.prepend("\x1b[44m");
+    content.append("\x1b[0m");
+
+    content
+}
+
 fn add_cursor_styles(
     vp: &ViewPort,
     mode: &Mode,
@@ -91,7 +133,7 @@ fn add_cursor_styles(
         let (code, reset) = match mode {
             Mode::Command(_) | Mode::Normal => ("\x1b[7m", reset.as_str()),
             Mode::Insert => ("\x1b[4m", reset.as_str()),
-            Mode::Navigation => ("", ""),
+            Mode::Navigation | Mode::Visual => ("", ""),
         };
 
         content.insert(cursor_index, code);
@@ -0,0 +1,17 @@
+use crate::model::{ansi::Ansi, viewport::ViewPort, BufferLine};
+
+pub fn get_custom_suffix(vp: &ViewPort, line: &BufferLine, content: &Ansi) -> Ansi {
+    let suffix = match &line.suffix {
+        Some(it) if vp.show_suffix => it,
+        _ => return Ansi::new(""),
+    };
+
+    let content_width = vp.get_content_width(line);
+    let padding = content_width.saturating_sub(content.count_chars());
+
+    Ansi::new(&format!(
+        "{} \x1b[90m{}\x1b[0m",
+        " ".repeat(padding),
+        suffix
+    ))
+}
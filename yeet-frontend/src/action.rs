@@ -10,7 +10,7 @@ use yeet_keymap::message::{KeymapMessage, QuitMode};
 use crate::{
     error::AppError,
     event::{Emitter, Message},
-    init::{history, mark, qfix},
+    init::{commandline, history, journal, mark, qfix},
     model::{DirectoryBufferState, Model, WindowType},
     open,
     task::Task,
@@ -35,6 +35,10 @@ pub fn emit_keymap(message: KeymapMessage) -> Action {
     Action::EmitMessages(vec![Message::Keymap(message)])
 }
 
+pub fn emit_error(message: &str) -> Action {
+    Action::EmitMessages(vec![Message::Error(message.to_owned())])
+}
+
 #[derive(PartialEq)]
 pub enum ActionResult {
     Normal,
@@ -114,6 +118,8 @@ async fn execute(
                 emitter.run(Task::EmitMessages(messages));
             }
             Action::Load(window_type, path, selection) => {
+                emitter.set_columns(model.settings.columns.clone()).await;
+
                 match window_type {
                     WindowType::Current => {
                         model.files.current.state = DirectoryBufferState::Loading;
@@ -140,13 +146,23 @@ async fn execute(
                             &BufferMessage::ResetCursor,
                         );
 
-                        emitter.run(Task::EnumerateDirectory(path, selection.clone()));
+                        emitter.run(Task::EnumerateDirectory(
+                            path,
+                            selection.clone(),
+                            model.settings.get_visibility(),
+                            model.settings.columns.clone(),
+                        ));
                     }
                     WindowType::Parent | WindowType::Preview => {
                         update::buffer_type(&window_type, model, path.as_path(), vec![]);
 
                         if path.is_dir() {
-                            emitter.run(Task::EnumerateDirectory(path.clone(), selection.clone()));
+                            emitter.run(Task::EnumerateDirectory(
+                                path.clone(),
+                                selection.clone(),
+                                model.settings.get_visibility(),
+                                model.settings.columns.clone(),
+                            ));
                         } else {
                             emitter.run(Task::LoadPreview(path.clone(), model.layout.preview));
                         }
@@ -192,6 +208,14 @@ async fn execute(
                         if let Err(error) = qfix::save_qfix_to_files(&model.qfix) {
                             tracing::error!("Failed to save quick fix to file: {:?}", error);
                         }
+                        if let Err(error) = journal::save_journal_to_file(&model.journal) {
+                            tracing::error!("Failed to save operation journal: {:?}", error);
+                        }
+                        if let Err(error) = commandline::save_commandline_history_to_file(
+                            &model.commandline.history,
+                        ) {
+                            tracing::error!("Failed to save commandline history: {:?}", error);
+                        }
                     }
                     QuitMode::Force => {}
                 };
@@ -209,10 +233,15 @@ async fn execute(
                     continue;
                 }
 
-                if let Some(cancellation) = model
-                    .current_tasks
-                    .get(&Task::EnumerateDirectory(path.clone(), None).to_string())
-                {
+                if let Some(cancellation) = model.current_tasks.get(
+                    &Task::EnumerateDirectory(
+                        path.clone(),
+                        None,
+                        model.settings.get_visibility(),
+                        model.settings.columns.clone(),
+                    )
+                    .to_string(),
+                ) {
                     cancellation.token.cancel();
                 };
 
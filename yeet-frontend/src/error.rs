@@ -16,8 +16,14 @@ pub enum AppError {
     InvalidMimeType,
     #[error("Path target is invalid")]
     InvalidTargetPath,
+    #[error("Loading commandline history failed")]
+    LoadCommandLineHistoryFailed,
+    #[error("Loading config failed: {0}")]
+    LoadConfigFailed(String),
     #[error("Loading navigation history failed")]
     LoadHistoryFailed,
+    #[error("Loading operation journal failed")]
+    LoadJournalFailed,
     #[error("Loading marks failed")]
     LoadMarkFailed,
     #[error("Loading quickfix failed")]
@@ -20,6 +20,7 @@ use tokio_util::sync::CancellationToken;
 use yeet_buffer::model::Mode;
 use yeet_keymap::{
     conversion,
+    map::KeyMap,
     message::{KeySequence, KeymapMessage},
     MessageResolver,
 };
@@ -27,7 +28,9 @@ use yeet_keymap::{
 use crate::{
     error::AppError,
     init::junkyard::get_junkyard_path,
-    task::{Task, TaskManager},
+    model::git::GitStatus,
+    settings::Column,
+    task::{column::get_formatted_columns, Task, TaskManager},
 };
 
 #[derive(Debug)]
@@ -60,17 +63,31 @@ pub enum MessageSource {
 
 pub enum Message {
     Keymap(KeymapMessage),
-    EnumerationChanged(PathBuf, Vec<(ContentKind, String)>, Option<String>),
-    EnumerationFinished(PathBuf, Vec<(ContentKind, String)>, Option<String>),
+    EnumerationChanged(
+        PathBuf,
+        Vec<(ContentKind, String, Option<String>)>,
+        Option<String>,
+    ),
+    EnumerationFinished(
+        PathBuf,
+        Vec<(ContentKind, String, Option<String>)>,
+        Option<String>,
+    ),
     Error(String),
     FdResult(Vec<PathBuf>),
+    FinderPathsAdded(PathBuf, Vec<PathBuf>),
+    GitStatusLoaded(PathBuf, Vec<(PathBuf, GitStatus)>),
+    GitStatusUnavailable(PathBuf),
+    GrepResult(Vec<(PathBuf, usize, String)>),
     PathRemoved(PathBuf),
-    PathsAdded(Vec<PathBuf>),
+    PathsAdded(Vec<(PathBuf, Option<String>)>),
     PreviewLoaded(Preview),
     Rerender,
     Resize(u16, u16),
     TaskStarted(String, CancellationToken),
     TaskEnded(String),
+    TaskFailed(String, String),
+    TaskProgress(String, usize, usize),
     ZoxideResult(PathBuf),
 }
 
@@ -86,13 +103,28 @@ impl std::fmt::Debug for Message {
             }
             Message::Error(err) => write!(f, "Error({:?})", err),
             Message::FdResult(paths) => write!(f, "FdResult({:?})", paths),
+            Message::FinderPathsAdded(root, paths) => {
+                write!(f, "FinderPathsAdded({:?}, {})", root, paths.len())
+            }
+            Message::GitStatusLoaded(root, _) => write!(f, "GitStatusLoaded({:?}, _)", root),
+            Message::GitStatusUnavailable(path) => write!(f, "GitStatusUnavailable({:?})", path),
+            Message::GrepResult(matches) => write!(f, "GrepResult({})", matches.len()),
             Message::PathRemoved(path) => write!(f, "PathRemoved({:?})", path),
-            Message::PathsAdded(paths) => write!(f, "PathsAdded({:?})", paths),
+            Message::PathsAdded(paths) => {
+                let paths: Vec<_> = paths.iter().map(|(path, _)| path).collect();
+                write!(f, "PathsAdded({:?})", paths)
+            }
             Message::PreviewLoaded(preview) => write!(f, "PreviewLoaded({:?})", preview),
             Message::Rerender => write!(f, "Rerender"),
             Message::Resize(x, y) => write!(f, "Resize({}, {})", x, y),
             Message::TaskStarted(identifier, _) => write!(f, "TaskStarted({})", identifier),
             Message::TaskEnded(identifier) => write!(f, "TaskEnded({})", identifier),
+            Message::TaskFailed(identifier, reason) => {
+                write!(f, "TaskFailed({}, {})", identifier, reason)
+            }
+            Message::TaskProgress(identifier, done, total) => {
+                write!(f, "TaskProgress({}, {}, {})", identifier, done, total)
+            }
             Message::ZoxideResult(path) => write!(f, "ZoxideResult({:?})", path),
         }
     }
@@ -127,15 +159,72 @@ impl PartialEq for Preview {
     }
 }
 
-// TODO: replace with ansi string
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ContentKind {
+    BlockDevice,
+    BrokenSymlink(PathBuf),
+    CharacterDevice,
     Directory,
+    Executable,
+    Fifo,
     File,
+    Socket,
+    Symlink(PathBuf),
+}
+
+impl ContentKind {
+    pub fn from_path(path: &Path) -> Self {
+        let metadata = match path.symlink_metadata() {
+            Ok(it) => it,
+            Err(_) => return ContentKind::File,
+        };
+
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            let target = path.read_link().unwrap_or_default();
+            return if path.exists() {
+                ContentKind::Symlink(target)
+            } else {
+                ContentKind::BrokenSymlink(target)
+            };
+        }
+
+        if file_type.is_dir() {
+            return ContentKind::Directory;
+        }
+
+        get_special_kind(&metadata).unwrap_or(ContentKind::File)
+    }
+}
+
+#[cfg(unix)]
+fn get_special_kind(metadata: &std::fs::Metadata) -> Option<ContentKind> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    let file_type = metadata.file_type();
+    if file_type.is_block_device() {
+        Some(ContentKind::BlockDevice)
+    } else if file_type.is_char_device() {
+        Some(ContentKind::CharacterDevice)
+    } else if file_type.is_fifo() {
+        Some(ContentKind::Fifo)
+    } else if file_type.is_socket() {
+        Some(ContentKind::Socket)
+    } else if metadata.permissions().mode() & 0o111 != 0 {
+        Some(ContentKind::Executable)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn get_special_kind(_metadata: &std::fs::Metadata) -> Option<ContentKind> {
+    None
 }
 
 pub struct Emitter {
     cancellation: CancellationToken,
+    columns: Arc<Mutex<Vec<Column>>>,
     crossterm_cancellation: CancellationToken,
     pub receiver: Receiver<Envelope>,
     resolver: Arc<Mutex<MessageResolver>>,
@@ -145,7 +234,7 @@ pub struct Emitter {
 }
 
 impl Emitter {
-    pub fn start(cancellation: CancellationToken) -> Self {
+    pub fn start(cancellation: CancellationToken, keymap: KeyMap) -> Self {
         let (sender, receiver) = mpsc::channel(1);
         let internal_sender = sender.clone();
 
@@ -157,11 +246,14 @@ impl Emitter {
         })
         .expect("Failed to create watcher");
 
-        let resolver = Arc::new(Mutex::new(MessageResolver::default()));
+        let resolver = Arc::new(Mutex::new(MessageResolver::new(keymap)));
 
         let (task_sender, mut task_receiver) = mpsc::channel(1);
         let tasks = TaskManager::new(task_sender, resolver.clone(), cancellation.child_token());
 
+        let columns = Arc::new(Mutex::new(Vec::new()));
+        let notify_columns = columns.clone();
+
         tokio::spawn(async move {
             loop {
                 let notify_event = notify_receiver.recv().fuse();
@@ -169,7 +261,8 @@ impl Emitter {
 
                 tokio::select! {
                     Some(Ok(event)) = notify_event => {
-                        if let Some(messages) = handle_notify_event(event) {
+                        let columns = notify_columns.lock().await.clone();
+                        if let Some(messages) = handle_notify_event(event, &columns) {
                             let _ = internal_sender.send(Envelope {
                                 messages,
                                 sequence: KeySequence::None,
@@ -195,6 +288,7 @@ impl Emitter {
 
         Self {
             cancellation,
+            columns,
             crossterm_cancellation,
             sender,
             tasks,
@@ -222,6 +316,11 @@ impl Emitter {
         self.cancellation.cancel();
     }
 
+    pub async fn set_columns(&mut self, columns: Vec<Column>) {
+        let mut current = self.columns.lock().await;
+        *current = columns;
+    }
+
     pub async fn set_current_mode(&mut self, mode: Mode) {
         let mut resolver = self.resolver.lock().await;
         resolver.mode = mode;
@@ -301,7 +400,7 @@ async fn handle_crossterm_event(
 }
 
 #[tracing::instrument]
-fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
+fn handle_notify_event(event: notify::Event, columns: &[Column]) -> Option<Vec<Message>> {
     if event.need_rescan() {
         // TODO: Refresh directory
     }
@@ -311,7 +410,7 @@ fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
             event
                 .paths
                 .iter()
-                .map(|p| Message::PathsAdded(vec![p.clone()]))
+                .map(|p| get_paths_added(p.clone(), columns))
                 .collect(),
         ),
         notify::EventKind::Modify(ModifyKind::Name(rename_mode)) => match rename_mode {
@@ -319,7 +418,7 @@ fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
                 if event.paths.len() == 2 {
                     Some(vec![
                         Message::PathRemoved(event.paths[0].clone()),
-                        Message::PathsAdded(vec![event.paths[1].clone()]),
+                        get_paths_added(event.paths[1].clone(), columns),
                     ])
                 } else {
                     tracing::warn!("event is invalid: {:?}", event);
@@ -336,7 +435,7 @@ fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
             }
             RenameMode::To => {
                 if event.paths.len() == 1 {
-                    Some(vec![Message::PathsAdded(vec![event.paths[0].clone()])])
+                    Some(vec![get_paths_added(event.paths[0].clone(), columns)])
                 } else {
                     tracing::warn!("event is invalid: {:?}", event);
                     None
@@ -346,7 +445,7 @@ fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
                 if event.paths.len() == 1 {
                     let path = event.paths[0].clone();
                     if path.exists() {
-                        Some(vec![Message::PathsAdded(vec![path])])
+                        Some(vec![get_paths_added(path, columns)])
                     } else {
                         Some(vec![Message::PathRemoved(path)])
                     }
@@ -376,3 +475,9 @@ fn handle_notify_event(event: notify::Event) -> Option<Vec<Message>> {
         }
     }
 }
+
+// NOTE: columns read metadata and user names, thus are formatted before reaching the model
+fn get_paths_added(path: PathBuf, columns: &[Column]) -> Message {
+    let formatted = get_formatted_columns(columns, &path);
+    Message::PathsAdded(vec![(path, formatted)])
+}
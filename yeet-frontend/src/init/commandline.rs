@@ -0,0 +1,97 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    path::Path,
+};
+
+use crate::{error::AppError, model::CommandLineHistory};
+
+const COMMAND_KIND: &str = ":";
+const SEARCH_KIND: &str = "/";
+
+#[tracing::instrument(skip(history))]
+pub fn load_commandline_history_from_file(
+    history: &mut CommandLineHistory,
+) -> Result<(), AppError> {
+    let history_path = get_commandline_history_path()?;
+    if !Path::new(&history_path).exists() {
+        tracing::debug!(
+            "commandline history file does not exist on path {}",
+            history_path
+        );
+
+        return Ok(());
+    }
+
+    // TODO: change to tokio fs
+    let history_file = File::open(history_path)?;
+    let mut history_csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(history_file);
+
+    for result in history_csv_reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(_) => return Err(AppError::LoadCommandLineHistoryFailed),
+        };
+
+        let entry = match record.get(1) {
+            Some(entry) => entry.to_owned(),
+            None => continue,
+        };
+
+        match record.get(0) {
+            Some(COMMAND_KIND) => history.commands.push(entry),
+            Some(SEARCH_KIND) => history.searches.push(entry),
+            _ => continue,
+        }
+    }
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(history))]
+pub fn save_commandline_history_to_file(history: &CommandLineHistory) -> Result<(), AppError> {
+    let history_path = get_commandline_history_path()?;
+    let history_dictionary = match Path::new(&history_path).parent() {
+        Some(path) => path,
+        None => return Err(AppError::LoadCommandLineHistoryFailed),
+    };
+
+    fs::create_dir_all(history_dictionary)?;
+
+    let history_writer = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(history_path)?;
+
+    let entries = history
+        .commands
+        .iter()
+        .map(|entry| (COMMAND_KIND, entry))
+        .chain(history.searches.iter().map(|entry| (SEARCH_KIND, entry)));
+
+    let mut writer = csv::Writer::from_writer(history_writer);
+    for (kind, entry) in entries {
+        let write_result = writer.write_record([kind, entry.as_str()]);
+        if let Err(error) = write_result {
+            tracing::error!("writing commandline history failed: {:?}", error);
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn get_commandline_history_path() -> Result<String, AppError> {
+    let cache_dir = match dirs::cache_dir() {
+        Some(cache_dir) => match cache_dir.to_str() {
+            Some(cache_dir_string) => cache_dir_string.to_string(),
+            None => return Err(AppError::LoadCommandLineHistoryFailed),
+        },
+        None => return Err(AppError::LoadCommandLineHistoryFailed),
+    };
+
+    Ok(format!("{}{}", cache_dir, "/yeet/commandline"))
+}
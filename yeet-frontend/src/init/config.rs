@@ -0,0 +1,282 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::Deserialize;
+use yeet_buffer::model::{
+    viewport::{LineNumber, WindowSettings},
+    CommandMode, Mode, SearchDirection,
+};
+use yeet_keymap::map::KeyMap;
+
+use crate::{
+    error::AppError,
+    settings::{Column, Settings},
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    columns: Option<Vec<ColumnConfig>>,
+    confirm_operations: Option<bool>,
+    respect_ignore: Option<bool>,
+    show_border: Option<bool>,
+    show_git_signs: Option<bool>,
+    show_hidden: Option<bool>,
+    show_mark_signs: Option<bool>,
+    show_quickfix_signs: Option<bool>,
+    window: WindowConfigs,
+    keymap: KeymapConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ColumnConfig {
+    Mtime,
+    Owner,
+    Permissions,
+    Size,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WindowConfigs {
+    current: WindowConfig,
+    parent: WindowConfig,
+    preview: WindowConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WindowConfig {
+    line_number: Option<LineNumberConfig>,
+    line_number_width: Option<usize>,
+    sign_column_width: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LineNumberConfig {
+    Absolute,
+    None,
+    Relative,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeymapConfig {
+    command: BTreeMap<String, String>,
+    insert: BTreeMap<String, String>,
+    navigation: BTreeMap<String, String>,
+    normal: BTreeMap<String, String>,
+    search: BTreeMap<String, String>,
+}
+
+#[tracing::instrument(skip(settings, keymap))]
+pub fn load_config(settings: &mut Settings, keymap: &mut KeyMap) -> Result<(), AppError> {
+    let config_path = match &settings.config_path {
+        Some(path) => path.clone(),
+        None => match get_default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => {
+                tracing::debug!("no config file found, using defaults");
+                return Ok(());
+            }
+        },
+    };
+
+    let content = match fs::read_to_string(&config_path) {
+        Ok(it) => it,
+        Err(err) => {
+            return Err(AppError::LoadConfigFailed(format!(
+                "{}: {}",
+                config_path.to_string_lossy(),
+                err
+            )))
+        }
+    };
+
+    let config = parse_config(&content).map_err(|err| {
+        AppError::LoadConfigFailed(format!("{}: {}", config_path.to_string_lossy(), err))
+    })?;
+
+    tracing::trace!("config file read: {:?}", config);
+
+    apply_config(config, settings, keymap)
+}
+
+fn get_default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("yeet").join("config.toml"))
+}
+
+fn parse_config(content: &str) -> Result<Config, toml::de::Error> {
+    toml::from_str(content)
+}
+
+fn apply_config(
+    config: Config,
+    settings: &mut Settings,
+    keymap: &mut KeyMap,
+) -> Result<(), AppError> {
+    if let Some(columns) = config.columns {
+        settings.columns = columns
+            .into_iter()
+            .map(|column| match column {
+                ColumnConfig::Mtime => Column::Mtime,
+                ColumnConfig::Owner => Column::Owner,
+                ColumnConfig::Permissions => Column::Permissions,
+                ColumnConfig::Size => Column::Size,
+            })
+            .collect();
+    }
+    if let Some(confirm_operations) = config.confirm_operations {
+        settings.confirm_operations = confirm_operations;
+    }
+    if let Some(respect_ignore) = config.respect_ignore {
+        settings.respect_ignore = respect_ignore;
+    }
+    if let Some(show_border) = config.show_border {
+        settings.show_border = show_border;
+    }
+    if let Some(show_git_signs) = config.show_git_signs {
+        settings.show_git_signs = show_git_signs;
+    }
+    if let Some(show_hidden) = config.show_hidden {
+        settings.show_hidden = show_hidden;
+    }
+    if let Some(show_mark_signs) = config.show_mark_signs {
+        settings.show_mark_signs = show_mark_signs;
+    }
+    if let Some(show_quickfix_signs) = config.show_quickfix_signs {
+        settings.show_quickfix_signs = show_quickfix_signs;
+    }
+
+    apply_window_config(config.window.current, &mut settings.current);
+    apply_window_config(config.window.parent, &mut settings.parent);
+    apply_window_config(config.window.preview, &mut settings.preview);
+
+    let remaps = [
+        (
+            vec![Mode::Command(CommandMode::Command)],
+            config.keymap.command,
+        ),
+        (vec![Mode::Insert], config.keymap.insert),
+        (vec![Mode::Navigation], config.keymap.navigation),
+        (vec![Mode::Normal], config.keymap.normal),
+        (
+            vec![
+                Mode::Command(CommandMode::Search(SearchDirection::Down)),
+                Mode::Command(CommandMode::Search(SearchDirection::Up)),
+            ],
+            config.keymap.search,
+        ),
+    ];
+
+    let mut errors = Vec::new();
+    for (modes, mappings) in remaps {
+        for (keys, target) in mappings {
+            for mode in &modes {
+                if let Err(err) = keymap.remap(mode, &keys, &target) {
+                    errors.push(AppError::LoadConfigFailed(format!(
+                        "keymap {} = {}: {}",
+                        keys, target, err
+                    )));
+                    break;
+                }
+            }
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(AppError::Aggregate(errors)),
+    }
+}
+
+fn apply_window_config(config: WindowConfig, settings: &mut WindowSettings) {
+    if let Some(line_number) = config.line_number {
+        settings.line_number = match line_number {
+            LineNumberConfig::Absolute => LineNumber::Absolute,
+            LineNumberConfig::None => LineNumber::None,
+            LineNumberConfig::Relative => LineNumber::Relative,
+        };
+    }
+    if let Some(line_number_width) = config.line_number_width {
+        settings.line_number_width = line_number_width;
+    }
+    if let Some(sign_column_width) = config.sign_column_width {
+        settings.sign_column_width = sign_column_width;
+    }
+}
+
+mod test {
+    #[test]
+    fn apply_config_settings() {
+        use yeet_buffer::model::viewport::LineNumber;
+        use yeet_keymap::map::KeyMap;
+
+        use crate::settings::{Column, Settings};
+
+        let config = super::parse_config(
+            r#"
+            columns = ["permissions", "size"]
+            confirm_operations = true
+            show_border = false
+            show_git_signs = false
+            show_hidden = false
+            show_mark_signs = false
+
+            [window.current]
+            line_number = "absolute"
+            line_number_width = 4
+
+            [window.preview]
+            sign_column_width = 0
+            "#,
+        )
+        .unwrap();
+
+        let mut settings = Settings::default();
+        let result = super::apply_config(config, &mut settings, &mut KeyMap::default());
+
+        assert!(result.is_ok());
+        assert_eq!(settings.columns, vec![Column::Permissions, Column::Size]);
+        assert!(settings.confirm_operations);
+        assert!(!settings.show_border);
+        assert!(!settings.show_git_signs);
+        assert!(!settings.show_hidden);
+        assert!(!settings.respect_ignore);
+        assert!(!settings.show_mark_signs);
+        assert!(settings.show_quickfix_signs);
+        assert_eq!(settings.current.line_number, LineNumber::Absolute);
+        assert_eq!(settings.current.line_number_width, 4);
+        assert_eq!(settings.current.sign_column_width, 2);
+        assert_eq!(settings.preview.sign_column_width, 0);
+    }
+
+    #[test]
+    fn apply_config_invalid_keymap() {
+        use yeet_keymap::map::KeyMap;
+
+        use crate::{error::AppError, settings::Settings};
+
+        let config = super::parse_config(
+            r#"
+            [keymap.normal]
+            "<C-s>" = ":w<CR>"
+            "<nope>" = "dd"
+            "#,
+        )
+        .unwrap();
+
+        let mut settings = Settings::default();
+        let result = super::apply_config(config, &mut settings, &mut KeyMap::default());
+
+        assert!(matches!(result, Err(AppError::LoadConfigFailed(_))));
+    }
+
+    #[test]
+    fn parse_config_unknown_field() {
+        let result = super::parse_config("show_borders = true");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::AppError,
+    model::{
+        journal::{Journal, JournalEntry, JournalGroup},
+        junkyard::{FileEntry, FileEntryStatus},
+    },
+};
+
+#[tracing::instrument]
+pub fn load_journal_from_file(journal: &mut Journal) -> Result<(), AppError> {
+    let journal_path = get_journal_path()?;
+    if !Path::new(&journal_path).exists() {
+        tracing::debug!("journal file does not exist on path {}", journal_path);
+
+        return Ok(());
+    }
+
+    let journal_file = File::open(journal_path)?;
+    let mut journal_csv_reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(journal_file);
+
+    tracing::trace!("journal file opened for reading");
+
+    for result in journal_csv_reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(_) => return Err(AppError::LoadJournalFailed),
+        };
+
+        let (id, kind, first) = match (record.get(0), record.get(1), record.get(2)) {
+            (Some(id), Some(kind), Some(first)) => (id, kind, PathBuf::from(first)),
+            _ => continue,
+        };
+
+        let entry = match (kind, record.get(3)) {
+            ("added", _) => JournalEntry::Added(first),
+            ("deleted", _) => JournalEntry::Deleted(first),
+            ("renamed", Some(second)) => JournalEntry::Renamed(first, PathBuf::from(second)),
+            ("trashed", Some(cache)) => {
+                let cache = PathBuf::from(cache);
+                let id = match cache.file_name() {
+                    Some(it) => it.to_string_lossy().to_string(),
+                    None => continue,
+                };
+
+                JournalEntry::Trashed(FileEntry {
+                    id,
+                    cache,
+                    status: FileEntryStatus::Ready,
+                    target: first,
+                })
+            }
+            _ => continue,
+        };
+
+        match journal.groups.last_mut() {
+            Some(group) if group.id == id => group.entries.push(entry),
+            _ => journal.groups.push(JournalGroup {
+                id: id.to_owned(),
+                entries: vec![entry],
+            }),
+        }
+    }
+
+    tracing::trace!("journal file read");
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(journal))]
+pub fn save_journal_to_file(journal: &Journal) -> Result<(), AppError> {
+    let journal_path = get_journal_path()?;
+    let journal_dictionary = match Path::new(&journal_path).parent() {
+        Some(path) => path,
+        None => return Err(AppError::LoadJournalFailed),
+    };
+
+    fs::create_dir_all(journal_dictionary)?;
+
+    let journal_writer = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(journal_path)?;
+
+    tracing::trace!("journal file opened for writing");
+
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(journal_writer);
+
+    for group in journal.groups.iter() {
+        for entry in group.entries.iter() {
+            let record = match entry {
+                JournalEntry::Added(path) => vec![
+                    group.id.as_str(),
+                    "added",
+                    path.to_str().unwrap_or_default(),
+                ],
+                JournalEntry::Deleted(path) => vec![
+                    group.id.as_str(),
+                    "deleted",
+                    path.to_str().unwrap_or_default(),
+                ],
+                JournalEntry::Renamed(old, new) => vec![
+                    group.id.as_str(),
+                    "renamed",
+                    old.to_str().unwrap_or_default(),
+                    new.to_str().unwrap_or_default(),
+                ],
+                JournalEntry::Trashed(entry) => vec![
+                    group.id.as_str(),
+                    "trashed",
+                    entry.target.to_str().unwrap_or_default(),
+                    entry.cache.to_str().unwrap_or_default(),
+                ],
+            };
+
+            if let Err(error) = writer.write_record(record) {
+                tracing::error!("writing journal entry failed: {:?}", error);
+            }
+        }
+    }
+
+    writer.flush()?;
+
+    tracing::trace!("journal file written");
+
+    Ok(())
+}
+
+fn get_journal_path() -> Result<String, AppError> {
+    let cache_dir = match dirs::cache_dir() {
+        Some(cache_dir) => match cache_dir.to_str() {
+            Some(cache_dir_string) => cache_dir_string.to_string(),
+            None => return Err(AppError::LoadJournalFailed),
+        },
+        None => return Err(AppError::LoadJournalFailed),
+    };
+
+    Ok(format!("{}{}", cache_dir, "/yeet/journal"))
+}
@@ -1,6 +1,6 @@
 use std::{
     fs::File,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     time,
 };
 
@@ -85,6 +85,37 @@ pub fn restore(entry: FileEntry, path: PathBuf) -> Result<(), AppError> {
     Ok(())
 }
 
+pub fn restore_as(entry: FileEntry, target: PathBuf) -> Result<(), AppError> {
+    let archive_file = File::open(entry.cache)?;
+    let archive_decoder = GzDecoder::new(archive_file);
+    let mut archive = Archive::new(archive_decoder);
+
+    // NOTE: the first component is the archived file name, which gets replaced with the target
+    for archive_entry in archive.entries()? {
+        let mut archive_entry = archive_entry?;
+        let relative: PathBuf = archive_entry.path()?.components().skip(1).collect();
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(AppError::InvalidTargetPath);
+        }
+
+        let path = if relative.as_os_str().is_empty() {
+            target.clone()
+        } else {
+            target.join(relative)
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        archive_entry.unpack(path)?;
+    }
+
+    Ok(())
+}
+
 async fn compress_with_archive_name(path: &Path, archive_name: &str) -> Result<(), AppError> {
     let compress_path = get_junk_compress_path().await?.join(archive_name);
 
@@ -1,4 +1,7 @@
+pub mod commandline;
+pub mod config;
 pub mod history;
+pub mod journal;
 pub mod junkyard;
 pub mod mark;
 pub mod qfix;
@@ -4,11 +4,12 @@ use action::{Action, ActionResult};
 use error::AppError;
 use event::{Emitter, Message, MessageSource};
 use init::{
-    history::load_history_from_file, junkyard::init_junkyard, mark::load_marks_from_file,
-    qfix::load_qfix_from_files,
+    commandline::load_commandline_history_from_file, config::load_config,
+    history::load_history_from_file, journal::load_journal_from_file, junkyard::init_junkyard,
+    mark::load_marks_from_file, qfix::load_qfix_from_files,
 };
 use layout::{AppLayout, CommandLineLayout};
-use model::{qfix::CdoState, Model};
+use model::{qfix::CdoState, Model, COMMANDLINE_HISTORY_WINDOW_HEIGHT};
 use settings::Settings;
 use task::Task;
 use terminal::TerminalWrapper;
@@ -16,8 +17,14 @@ use tokio_util::sync::CancellationToken;
 use update::update_model;
 use view::render_model;
 
-use yeet_buffer::{message::BufferMessage, model::Mode};
-use yeet_keymap::message::{KeymapMessage, PrintContent, QuitMode};
+use yeet_buffer::{
+    message::BufferMessage,
+    model::{CommandMode, Mode},
+};
+use yeet_keymap::{
+    map::KeyMap,
+    message::{KeymapMessage, PrintContent, QuitMode},
+};
 
 mod action;
 pub mod error;
@@ -32,10 +39,13 @@ mod terminal;
 mod update;
 mod view;
 
-pub async fn run(settings: Settings) -> Result<(), AppError> {
+pub async fn run(mut settings: Settings) -> Result<(), AppError> {
+    let mut keymap = KeyMap::default();
+    let config_result = load_config(&mut settings, &mut keymap);
+
     let cancellation = CancellationToken::new();
     let mut terminal = TerminalWrapper::start()?;
-    let mut emitter = Emitter::start(cancellation.child_token());
+    let mut emitter = Emitter::start(cancellation.child_token(), keymap);
 
     let initial_path = get_initial_path(&settings.startup_path);
     emitter.run(Task::EmitMessages(vec![
@@ -75,6 +85,30 @@ pub async fn run(settings: Settings) -> Result<(), AppError> {
         )]));
     }
 
+    if load_journal_from_file(&mut model.journal).is_err() {
+        emitter.run(Task::EmitMessages(vec![Message::Keymap(
+            KeymapMessage::Print(vec![PrintContent::Error(
+                "Failed to load operation journal".to_string(),
+            )]),
+        )]));
+    }
+
+    if load_commandline_history_from_file(&mut model.commandline.history).is_err() {
+        emitter.run(Task::EmitMessages(vec![Message::Keymap(
+            KeymapMessage::Print(vec![PrintContent::Error(
+                "Failed to load commandline history".to_string(),
+            )]),
+        )]));
+    }
+
+    if let Err(error) = config_result {
+        tracing::error!("loading config failed: {:?}", error);
+
+        emitter.run(Task::EmitMessages(vec![Message::Keymap(
+            KeymapMessage::Print(get_config_error_content(error)),
+        )]));
+    }
+
     tracing::debug!("starting with model state: {:?}", model);
 
     while let Some(envelope) = emitter.receiver.recv().await {
@@ -163,14 +197,48 @@ fn get_initial_path(initial_selection: &Option<PathBuf>) -> PathBuf {
     env::current_dir().expect("Failed to get current directory")
 }
 
+fn get_config_error_content(error: AppError) -> Vec<PrintContent> {
+    match error {
+        AppError::Aggregate(errors) => errors
+            .into_iter()
+            .flat_map(get_config_error_content)
+            .collect(),
+        error => error
+            .to_string()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| PrintContent::Error(line.to_owned()))
+            .collect(),
+    }
+}
+
 fn get_commandline_height(model: &Model, messages: &Vec<Message>) -> u16 {
     let lines_len = model.commandline.buffer.lines.len();
-    let mut height = if lines_len == 0 { 1 } else { lines_len as u16 };
+    let mut height = if lines_len == 0 {
+        1
+    } else if model.commandline.history.window {
+        lines_len.min(COMMANDLINE_HISTORY_WINDOW_HEIGHT) as u16
+    } else {
+        lines_len as u16
+    };
     for message in messages {
-        if let Message::Keymap(KeymapMessage::Print(content)) = message {
-            if content.len() > 1 {
+        match message {
+            Message::Keymap(KeymapMessage::Print(content)) if content.len() > 1 => {
                 height = content.len() as u16 + 1;
             }
+            Message::Keymap(KeymapMessage::OpenCommandLineHistory(mode)) => {
+                let history = &model.commandline.history;
+                let entries = match mode {
+                    CommandMode::Search(_) => history.searches.len(),
+                    CommandMode::Command | CommandMode::Find | CommandMode::PrintMultiline => {
+                        history.commands.len()
+                    }
+                };
+
+                // NOTE: plus one for the empty line to enter a new command
+                height = (entries + 1).min(COMMANDLINE_HISTORY_WINDOW_HEIGHT) as u16;
+            }
+            _ => {}
         }
     }
     height
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+pub const FINDER_HEIGHT: usize = 10;
+
+#[derive(Debug, Default)]
+pub struct Finder {
+    pub index: usize,
+    // NOTE: matches contain score, length and index of the path to sort without scoring again
+    pub matches: Vec<(usize, usize, usize)>,
+    pub paths: Vec<PathBuf>,
+    pub query: String,
+    pub root: PathBuf,
+}
+
+impl Finder {
+    pub fn get_selected_path(&self) -> Option<PathBuf> {
+        self.matches
+            .get(self.index)
+            .and_then(|(_, _, index)| self.paths.get(*index))
+            .map(|path| self.root.join(path))
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::model::SignIdentifier;
+
+pub const GIT_SIGN_ID: SignIdentifier = "git";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Git {
+    pub repositories: HashMap<PathBuf, HashMap<PathBuf, GitStatus>>,
+    pub unavailable: HashSet<PathBuf>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GitStatus {
+    Conflicted,
+    Ignored,
+    Modified,
+    Staged,
+    Untracked,
+}
+
+impl Git {
+    pub fn get_repository(&self, path: &Path) -> Option<&Path> {
+        // NOTE: the longest root wins to resolve nested repositories and submodules
+        self.repositories
+            .keys()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .map(|root| root.as_path())
+    }
+
+    pub fn get_status(&self, path: &Path) -> Option<&GitStatus> {
+        let root = self.get_repository(path)?;
+        let entries = self.repositories.get(root)?;
+        if let Some(status) = entries.get(path) {
+            return Some(status);
+        }
+
+        // NOTE: git reports untracked and ignored directories without their content
+        path.ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(root) && *ancestor != root)
+            .filter_map(|ancestor| entries.get(ancestor))
+            .find(|status| matches!(status, GitStatus::Ignored | GitStatus::Untracked))
+    }
+}
+
+impl GitStatus {
+    pub fn get_priority(&self) -> usize {
+        match self {
+            GitStatus::Ignored => 0,
+            GitStatus::Untracked => 1,
+            GitStatus::Staged => 2,
+            GitStatus::Modified => 3,
+            GitStatus::Conflicted => 4,
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn get_status_in_untracked_directory() {
+        use std::{collections::HashMap, path::PathBuf};
+
+        use super::{Git, GitStatus};
+
+        let mut entries = HashMap::new();
+        entries.insert(PathBuf::from("/repo/new"), GitStatus::Untracked);
+        entries.insert(PathBuf::from("/repo/src"), GitStatus::Modified);
+
+        let mut git = Git::default();
+        git.repositories.insert(PathBuf::from("/repo"), entries);
+
+        assert_eq!(
+            git.get_status(&PathBuf::from("/repo/new/file")),
+            Some(&GitStatus::Untracked)
+        );
+        assert_eq!(git.get_status(&PathBuf::from("/repo/src/lib.rs")), None);
+        assert_eq!(git.get_status(&PathBuf::from("/other/new")), None);
+    }
+}
@@ -0,0 +1,25 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use super::junkyard::FileEntry;
+
+pub const JOURNAL_SIZE: usize = 50;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Journal {
+    pub groups: Vec<JournalGroup>,
+    pub pending: HashMap<String, (String, Vec<JournalEntry>)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalGroup {
+    pub id: String,
+    pub entries: Vec<JournalEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalEntry {
+    Added(PathBuf),
+    Deleted(PathBuf),
+    Renamed(PathBuf, PathBuf),
+    Trashed(FileEntry),
+}
@@ -10,14 +10,24 @@ use crate::{
 use ratatui::layout::Rect;
 use ratatui_image::protocol::Protocol;
 use tokio_util::sync::CancellationToken;
-use yeet_buffer::model::{
-    viewport::{LineNumber, ViewPort},
-    Buffer, Cursor, Mode,
-};
+use yeet_buffer::model::{viewport::ViewPort, Buffer, Cursor, Mode, SignIdentifier};
+use yeet_keymap::message::QuitMode;
 
-use self::{history::History, junkyard::JunkYard, mark::Marks, qfix::QuickFix, register::Register};
+use self::{
+    finder::Finder,
+    git::Git,
+    history::History,
+    journal::Journal,
+    junkyard::{FileEntry, JunkYard},
+    mark::Marks,
+    qfix::QuickFix,
+    register::Register,
+};
 
+pub mod finder;
+pub mod git;
 pub mod history;
+pub mod journal;
 pub mod junkyard;
 pub mod mark;
 pub mod qfix;
@@ -28,13 +38,17 @@ pub struct Model {
     pub commandline: CommandLine,
     pub current_tasks: HashMap<String, CurrentTask>,
     pub files: FileWindow,
+    pub finder: Option<Finder>,
+    pub git: Git,
     pub history: History,
+    pub journal: Journal,
     pub junk: JunkYard,
     pub latest_task_id: u16,
     pub layout: AppLayout,
     pub marks: Marks,
     pub mode: Mode,
     pub mode_before: Option<Mode>,
+    pub operations: FileOperations,
     pub qfix: QuickFix,
     pub register: Register,
     pub remaining_keysequence: Option<String>,
@@ -45,6 +59,7 @@ pub struct Model {
 impl std::fmt::Debug for Model {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Model")
+            .field("journal", &self.journal)
             .field("junk", &self.junk)
             .field("marks", &self.marks)
             .field("qfix", &self.qfix)
@@ -57,20 +72,33 @@ impl std::fmt::Debug for Model {
 pub struct CurrentTask {
     pub external_id: String,
     pub id: u16,
+    pub progress: Option<(usize, usize)>,
     pub token: CancellationToken,
 }
 
+#[derive(Debug, Default)]
+pub struct FileOperations {
+    pub failed: HashMap<PathBuf, String>,
+    pub pending: HashMap<String, Vec<PathBuf>>,
+}
+
 pub struct FileWindow {
+    pub archive: Option<ArchiveListing>,
+    pub bulk_rename: Option<BulkRename>,
     pub current: PathBuffer,
     pub current_vp: ViewPort,
     pub current_cursor: Option<Cursor>,
+    pub filters: HashMap<PathBuf, String>,
     pub parent: BufferType,
     pub parent_vp: ViewPort,
     pub parent_cursor: Option<Cursor>,
     pub preview: BufferType,
     pub preview_vp: ViewPort,
     pub preview_cursor: Option<Cursor>,
+    pub preview_focused: bool,
+    pub search_origin: Option<(Option<Cursor>, usize)>,
     pub show_border: bool,
+    pub visual_selection: Vec<PathBuf>,
 }
 
 impl FileWindow {
@@ -88,6 +116,18 @@ impl FileWindow {
             None
         };
 
+        // NOTE: while bulk renaming, current holds the paths to rename and must not be updated
+        let current_content_ref = if self.bulk_rename.is_none() {
+            Some((
+                self.current.path.as_path(),
+                &mut self.current_vp,
+                &mut self.current_cursor,
+                &mut self.current.buffer,
+            ))
+        } else {
+            None
+        };
+
         let preview_content_ref = if let BufferType::Text(path, buffer) = &mut self.preview {
             Some((
                 path.as_path(),
@@ -99,43 +139,50 @@ impl FileWindow {
             None
         };
 
-        vec![
-            Some((
-                self.current.path.as_path(),
-                &mut self.current_vp,
-                &mut self.current_cursor,
-                &mut self.current.buffer,
-            )),
-            parent_content_ref,
-            preview_content_ref,
-        ]
-        .into_iter()
-        .flatten()
-        .collect::<Vec<_>>()
+        vec![current_content_ref, parent_content_ref, preview_content_ref]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
     }
 }
 
 impl Default for FileWindow {
     fn default() -> Self {
         Self {
+            archive: None,
+            bulk_rename: None,
             current: Default::default(),
             current_cursor: Some(Cursor::default()),
-            current_vp: ViewPort {
-                line_number: LineNumber::Relative,
-                line_number_width: 3,
-                ..Default::default()
-            },
+            current_vp: Default::default(),
+            filters: HashMap::new(),
             parent: Default::default(),
             parent_vp: Default::default(),
             parent_cursor: Default::default(),
             preview: Default::default(),
             preview_vp: Default::default(),
             preview_cursor: Default::default(),
+            preview_focused: false,
+            search_origin: None,
             show_border: true,
+            visual_selection: Vec::new(),
         }
     }
 }
 
+// NOTE: the preview shows the entries of one directory level below prefix
+#[derive(Debug, Default)]
+pub struct ArchiveListing {
+    pub entries: Vec<String>,
+    pub path: PathBuf,
+    pub prefix: String,
+}
+
+#[derive(Debug)]
+pub struct BulkRename {
+    pub paths: Vec<PathBuf>,
+    pub root: PathBuf,
+}
+
 #[derive(Debug)]
 pub enum WindowType {
     Current,
@@ -166,9 +213,12 @@ impl BufferType {
 
 pub struct CommandLine {
     pub buffer: Buffer,
+    pub completion: Option<CommandLineCompletion>,
     pub cursor: Option<Cursor>,
+    pub history: CommandLineHistory,
     pub key_sequence: String,
     pub layout: CommandLineLayout,
+    pub prompt: Option<CommandLinePrompt>,
     pub viewport: ViewPort,
 }
 
@@ -182,13 +232,47 @@ impl Default for CommandLine {
                 ..Default::default()
             }),
             buffer: Default::default(),
+            completion: None,
+            history: Default::default(),
             key_sequence: "".to_owned(),
             layout: CommandLineLayout::new(Rect::default(), 0),
+            prompt: None,
             viewport: Default::default(),
         }
     }
 }
 
+pub const COMMANDLINE_COMPLETION_HEIGHT: usize = 8;
+
+#[derive(Debug)]
+pub struct CommandLineCompletion {
+    pub base: String,
+    pub candidates: Vec<String>,
+    pub index: usize,
+}
+
+pub const COMMANDLINE_HISTORY_WINDOW_HEIGHT: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct CommandLineHistory {
+    pub commands: Vec<String>,
+    pub index: Option<usize>,
+    pub prefix: String,
+    pub searches: Vec<String>,
+    pub window: bool,
+}
+
+#[derive(Debug)]
+pub enum CommandLinePrompt {
+    ConfirmPathChanges(Option<QuitMode>),
+    PasteConflict(Vec<FileEntry>, PathBuf),
+}
+
+pub const ERROR_SIGN_ID: SignIdentifier = "error";
+pub const FAILED_SIGN_ID: SignIdentifier = "failed";
+pub const PENDING_SIGN_ID: SignIdentifier = "pending";
+pub const REMOVED_SIGN_ID: SignIdentifier = "removed";
+
 #[derive(Default)]
 pub struct PathBuffer {
     pub buffer: Buffer,
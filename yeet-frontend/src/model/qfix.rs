@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use yeet_buffer::model::SignIdentifier;
 
@@ -7,8 +10,23 @@ pub const QFIX_SIGN_ID: SignIdentifier = "qfix";
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct QuickFix {
     pub current_index: usize,
+    pub current_match: usize,
     pub cdo: CdoState,
     pub entries: Vec<PathBuf>,
+    pub matches: HashMap<PathBuf, Vec<QuickFixMatch>>,
+}
+
+impl QuickFix {
+    pub fn remove(&mut self, path: &Path) {
+        self.entries.retain(|p| p != path);
+        self.matches.remove(path);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuickFixMatch {
+    pub line_number: usize,
+    pub text: String,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
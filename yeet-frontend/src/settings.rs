@@ -1,31 +1,96 @@
 use std::path::PathBuf;
 
-use yeet_buffer::model::viewport::WindowSettings;
+use lscolors::LsColors;
+use yeet_buffer::model::viewport::{LineNumber, WindowSettings};
+
+use crate::task::visibility::Visibility;
 
 #[derive(Debug)]
 pub struct Settings {
+    pub columns: Vec<Column>,
+    pub config_path: Option<PathBuf>,
+    pub confirm_operations: bool,
     pub current: WindowSettings,
+    pub ls_colors: LsColors,
     pub parent: WindowSettings,
     pub preview: WindowSettings,
     pub selection_to_file_on_open: Option<PathBuf>,
     pub selection_to_stdout_on_open: bool,
+    pub respect_ignore: bool,
+    pub show_border: bool,
+    pub show_git_signs: bool,
+    pub show_hidden: bool,
     pub show_quickfix_signs: bool,
     pub show_mark_signs: bool,
+    pub sort: SortSettings,
     pub startup_path: Option<PathBuf>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    Mtime,
+    Owner,
+    Permissions,
+    Size,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SortSettings {
+    pub directories_first: bool,
+    pub mode: SortMode,
+    pub reverse: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SortMode {
+    Ctime,
+    Extension,
+    Mtime,
+    #[default]
+    Name,
+    Natural,
+    Size,
+}
+
+impl Settings {
+    pub fn get_visibility(&self) -> Visibility {
+        Visibility {
+            show_hidden: self.show_hidden,
+            respect_ignore: self.respect_ignore,
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            columns: Vec::new(),
+            config_path: None,
+            confirm_operations: false,
             current: WindowSettings {
+                line_number: LineNumber::Relative,
+                line_number_width: 3,
+                show_suffix: true,
+                sign_column_width: 2,
+            },
+            ls_colors: LsColors::from_env().unwrap_or_default(),
+            parent: WindowSettings {
+                sign_column_width: 2,
+                ..Default::default()
+            },
+            preview: WindowSettings {
                 sign_column_width: 2,
+                ..Default::default()
             },
-            parent: WindowSettings::default(),
-            preview: WindowSettings::default(),
             selection_to_file_on_open: None,
             selection_to_stdout_on_open: false,
+            respect_ignore: false,
+            show_border: true,
+            show_git_signs: true,
+            show_hidden: true,
             show_mark_signs: true,
             show_quickfix_signs: true,
+            sort: Default::default(),
             startup_path: None,
         }
     }
@@ -0,0 +1,330 @@
+use std::{
+    fs::File,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use tar::Archive;
+
+use crate::{error::AppError, event::Preview};
+
+use super::conflict;
+
+// NOTE: the decompressed content of plain gzip files gets previewed up to this size
+const GZIP_PREVIEW_SIZE: u64 = 1024 * 1024;
+
+pub fn is_archive(path: &Path) -> bool {
+    matches!(
+        get_reader_kind(path),
+        Some(ReaderKind::Tar) | Some(ReaderKind::TarGzip)
+    )
+}
+
+// NOTE: returns None for paths which are no archive or compressed file
+pub async fn load(path: &Path) -> Option<Preview> {
+    let preview = match get_reader_kind(path)? {
+        ReaderKind::Gzip => decompress(path).await,
+        ReaderKind::Tar | ReaderKind::TarGzip => list(path).await,
+        ReaderKind::Unsupported(format) => Preview::Content(
+            path.to_path_buf(),
+            vec![format!("unsupported archive format: {}", format)],
+        ),
+    };
+
+    Some(preview)
+}
+
+async fn decompress(path: &Path) -> Preview {
+    let archive_path = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || get_decompressed_lines(&archive_path)).await;
+
+    match result {
+        Ok(Ok(lines)) => Preview::Content(path.to_path_buf(), lines),
+        Ok(Err(err)) => {
+            tracing::error!("decompressing file failed: {:?} {:?}", path, err);
+            Preview::None(path.to_path_buf())
+        }
+        Err(err) => {
+            tracing::error!("decompressing file failed: {:?} {:?}", path, err);
+            Preview::None(path.to_path_buf())
+        }
+    }
+}
+
+fn get_decompressed_lines(path: &Path) -> Result<Vec<String>, AppError> {
+    let mut content = String::new();
+    GzDecoder::new(File::open(path)?)
+        .take(GZIP_PREVIEW_SIZE)
+        .read_to_string(&mut content)?;
+
+    Ok(content.lines().map(|line| line.to_string()).collect())
+}
+
+async fn list(path: &Path) -> Preview {
+    let archive_path = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || get_entries(&archive_path)).await;
+
+    match result {
+        Ok(Ok(entries)) => Preview::Content(path.to_path_buf(), entries),
+        Ok(Err(err)) => {
+            tracing::error!("reading archive failed: {:?} {:?}", path, err);
+            Preview::None(path.to_path_buf())
+        }
+        Err(err) => {
+            tracing::error!("listing archive failed: {:?} {:?}", path, err);
+            Preview::None(path.to_path_buf())
+        }
+    }
+}
+
+pub async fn extract(path: PathBuf, entry: String, target: PathBuf) -> Result<(), AppError> {
+    let result = tokio::task::spawn_blocking(move || extract_entry(&path, &entry, &target)).await;
+
+    match result {
+        Ok(result) => result,
+        Err(err) => Err(AppError::ExecutionFailed(err.to_string())),
+    }
+}
+
+// NOTE: directory entries get extracted with all their children, existing targets get renamed
+fn extract_entry(path: &Path, entry: &str, target: &Path) -> Result<(), AppError> {
+    let selected = Path::new(entry.trim_end_matches('/'));
+    let file_name = match selected.file_name() {
+        Some(it) => it,
+        None => return Err(AppError::InvalidTargetPath),
+    };
+
+    let mut destination = target.join(file_name);
+    if destination.exists() {
+        destination = conflict::get_free_path(&destination);
+    }
+
+    let mut archive = open(path)?;
+    let mut is_extracted = false;
+    for archive_entry in archive.entries()? {
+        let mut archive_entry = archive_entry?;
+        let archive_entry_path = archive_entry.path()?.to_path_buf();
+        let relative = match archive_entry_path.strip_prefix(selected) {
+            Ok(it) => it.to_path_buf(),
+            Err(_) => continue,
+        };
+
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(AppError::InvalidTargetPath);
+        }
+
+        // NOTE: hard links and symlinks unpacked before could redirect writes outside the target
+        if archive_entry.header().entry_type().is_hard_link()
+            || is_symlink_in_path(&destination, &relative)
+        {
+            return Err(AppError::InvalidTargetPath);
+        }
+
+        let path = if relative.as_os_str().is_empty() {
+            destination.clone()
+        } else {
+            destination.join(relative)
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        archive_entry.unpack(path)?;
+        is_extracted = true;
+    }
+
+    if is_extracted {
+        Ok(())
+    } else {
+        Err(AppError::InvalidTargetPath)
+    }
+}
+
+fn is_symlink_in_path(destination: &Path, relative: &Path) -> bool {
+    let parent = match relative.parent() {
+        Some(it) => it,
+        None => return false,
+    };
+
+    let mut current = destination.to_path_buf();
+    for component in parent.components() {
+        current.push(component);
+        if current
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+enum ReaderKind {
+    Gzip,
+    Tar,
+    TarGzip,
+    Unsupported(&'static str),
+}
+
+const UNSUPPORTED_FORMATS: [&str; 8] = [
+    ".7z", ".bz2", ".rar", ".tar.bz2", ".tar.xz", ".tar.zst", ".xz", ".zip",
+];
+
+fn get_reader_kind(path: &Path) -> Option<ReaderKind> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar") {
+        Some(ReaderKind::Tar)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ReaderKind::TarGzip)
+    } else if name.ends_with(".gz") {
+        Some(ReaderKind::Gzip)
+    } else {
+        UNSUPPORTED_FORMATS
+            .iter()
+            .filter(|format| name.ends_with(*format))
+            .max_by_key(|format| format.len())
+            .map(|format| ReaderKind::Unsupported(format))
+    }
+}
+
+fn open(path: &Path) -> Result<Archive<Box<dyn Read>>, AppError> {
+    let file = File::open(path)?;
+    let reader: Box<dyn Read> = match get_reader_kind(path) {
+        Some(ReaderKind::TarGzip) => Box::new(GzDecoder::new(file)),
+        Some(ReaderKind::Tar) => Box::new(file),
+        Some(ReaderKind::Gzip) | Some(ReaderKind::Unsupported(_)) | None => {
+            return Err(AppError::InvalidMimeType)
+        }
+    };
+
+    Ok(Archive::new(reader))
+}
+
+fn get_entries(path: &Path) -> Result<Vec<String>, AppError> {
+    let mut archive = open(path)?;
+
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let mut name = entry.path()?.to_string_lossy().to_string();
+        if entry.header().entry_type().is_dir() && !name.ends_with('/') {
+            name.push('/');
+        }
+        entries.push(name);
+    }
+
+    Ok(entries)
+}
+
+mod test {
+    #[test]
+    fn list_and_extract_directory_entry() {
+        use std::fs::{self, File};
+
+        use flate2::{write::GzEncoder, Compression};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let source = root.join("source");
+        fs::create_dir_all(source.join("dir")).unwrap();
+        fs::write(source.join("dir").join("inner.txt"), "inner").unwrap();
+        fs::write(source.join("top.txt"), "top").unwrap();
+
+        let path = root.join("test.tar.gz");
+        let encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all("", &source).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let mut entries = super::get_entries(&path).unwrap();
+        entries.sort();
+
+        let target = root.join("target");
+        fs::create_dir_all(&target).unwrap();
+        super::extract_entry(&path, "dir/", &target).unwrap();
+        super::extract_entry(&path, "dir/", &target).unwrap();
+
+        let inner = fs::read_to_string(target.join("dir").join("inner.txt")).ok();
+        let renamed = target.join("dir (1)").join("inner.txt").exists();
+        let is_archive = super::is_archive(&path);
+
+        assert!(entries.contains(&"dir/".to_owned()));
+        assert!(entries.contains(&"dir/inner.txt".to_owned()));
+        assert!(entries.contains(&"top.txt".to_owned()));
+        assert_eq!(Some("inner".to_owned()), inner);
+        assert!(renamed);
+        assert!(is_archive);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extract_rejects_paths_through_symlinks() {
+        use std::fs::{self, File};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let outside = root.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+
+        let path = root.join("test.tar");
+        let mut builder = tar::Builder::new(File::create(&path).unwrap());
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder
+            .append_link(&mut header, "d/link", &outside)
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(6);
+        builder
+            .append_data(&mut header, "d/link/passwd", "passwd".as_bytes())
+            .unwrap();
+        builder.into_inner().unwrap();
+
+        let target = root.join("target");
+        fs::create_dir_all(&target).unwrap();
+        let result = super::extract_entry(&path, "d/", &target);
+
+        assert!(result.is_err());
+        assert!(!outside.join("passwd").exists());
+    }
+
+    #[tokio::test]
+    async fn load_gzip_and_unsupported_formats() {
+        use std::{fs::File, io::Write};
+
+        use flate2::{write::GzEncoder, Compression};
+
+        use crate::event::Preview;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+
+        let path = root.join("notes.txt.gz");
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(b"first\nsecond\n").unwrap();
+        encoder.finish().unwrap();
+
+        let gzip = super::load(&path).await;
+        let zip = super::load(&root.join("files.zip")).await;
+        let text = super::load(&root.join("files.txt")).await;
+
+        assert!(!super::is_archive(&path));
+        assert!(matches!(
+            gzip,
+            Some(Preview::Content(_, lines)) if lines == vec!["first", "second"]
+        ));
+        assert!(matches!(
+            zip,
+            Some(Preview::Content(_, lines)) if lines == vec!["unsupported archive format: .zip"]
+        ));
+        assert!(text.is_none());
+    }
+}
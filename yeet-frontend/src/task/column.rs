@@ -0,0 +1,151 @@
+use std::{fs::Metadata, path::Path, time::SystemTime};
+
+use chrono::{DateTime, Local};
+use yeet_buffer::model::BufferLine;
+
+use crate::settings::Column;
+
+const SIZE_UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+
+pub fn add_columns(bl: &mut BufferLine, columns: &str) {
+    // NOTE: columns go first to keep them aligned, e.g. in front of symlink targets
+    bl.suffix = match &bl.suffix {
+        Some(suffix) => Some(format!("{} {}", columns, suffix)),
+        None => Some(columns.to_owned()),
+    };
+}
+
+pub fn get_formatted_columns(columns: &[Column], path: &Path) -> Option<String> {
+    if columns.is_empty() {
+        return None;
+    }
+
+    let metadata = match path.symlink_metadata() {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::warn!("reading metadata for {:?} failed: {:?}", path, err);
+            return None;
+        }
+    };
+
+    let columns = columns
+        .iter()
+        .map(|column| match column {
+            Column::Mtime => get_mtime(&metadata),
+            Column::Owner => format!("{:<8}", get_owner(&metadata)),
+            Column::Permissions => get_permissions(&metadata),
+            Column::Size => format!("{:>5}", get_size(&metadata)),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(columns)
+}
+
+fn get_mtime(metadata: &Metadata) -> String {
+    match metadata.modified() {
+        Ok(time) => get_formatted_time(time),
+        Err(_) => format!("{:<16}", "-"),
+    }
+}
+
+fn get_formatted_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+#[cfg(unix)]
+fn get_owner(metadata: &Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+
+    let uid = metadata.uid();
+    match uzers::get_user_by_uid(uid) {
+        Some(user) => user.name().to_string_lossy().to_string(),
+        None => uid.to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+fn get_owner(_metadata: &Metadata) -> String {
+    "-".to_owned()
+}
+
+#[cfg(unix)]
+fn get_permissions(metadata: &Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    get_formatted_mode(metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn get_permissions(metadata: &Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "r--r--r--".to_owned()
+    } else {
+        "rw-rw-rw-".to_owned()
+    }
+}
+
+#[cfg(unix)]
+fn get_formatted_mode(mode: u32) -> String {
+    let flags = ['r', 'w', 'x'];
+    (0..9)
+        .map(|index| {
+            if mode & (0o400 >> index) == 0 {
+                '-'
+            } else {
+                flags[index % 3]
+            }
+        })
+        .collect()
+}
+
+fn get_size(metadata: &Metadata) -> String {
+    if metadata.is_dir() {
+        return "-".to_owned();
+    }
+
+    get_formatted_size(metadata.len())
+}
+
+fn get_formatted_size(size: u64) -> String {
+    if size < 1024 {
+        return size.to_string();
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit = SIZE_UNITS[0];
+    for next in SIZE_UNITS.iter().skip(1) {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    if value < 10.0 {
+        format!("{:.1}{}", value, unit)
+    } else {
+        format!("{:.0}{}", value, unit)
+    }
+}
+
+mod test {
+    #[test]
+    fn get_formatted_size() {
+        assert_eq!(super::get_formatted_size(0), "0");
+        assert_eq!(super::get_formatted_size(1023), "1023");
+        assert_eq!(super::get_formatted_size(1024), "1.0K");
+        assert_eq!(super::get_formatted_size(15 * 1024 + 512), "16K");
+        assert_eq!(super::get_formatted_size(3 * 1024 * 1024 * 1024), "3.0G");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn get_formatted_mode() {
+        assert_eq!(super::get_formatted_mode(0o755), "rwxr-xr-x");
+        assert_eq!(super::get_formatted_mode(0o100644), "rw-r--r--");
+        assert_eq!(super::get_formatted_mode(0o000), "---------");
+    }
+}
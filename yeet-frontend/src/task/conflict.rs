@@ -0,0 +1,221 @@
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+
+use crate::error::AppError;
+
+use super::ConflictPolicy;
+
+#[derive(Debug)]
+pub struct Target {
+    pub path: PathBuf,
+    replaces: Option<PathBuf>,
+}
+
+pub async fn resolve(
+    source: Option<&Path>,
+    target: PathBuf,
+    policy: &ConflictPolicy,
+) -> Result<Option<Target>, AppError> {
+    if fs::symlink_metadata(&target).await.is_err() {
+        return Ok(Some(Target {
+            path: target,
+            replaces: None,
+        }));
+    }
+
+    let is_source = source.is_some_and(|source| source == target);
+    let is_nested = source.is_some_and(|source| {
+        source != target && (source.starts_with(&target) || target.starts_with(source))
+    });
+
+    match policy {
+        ConflictPolicy::Fail => Err(AppError::InvalidTargetPath),
+        ConflictPolicy::Overwrite if is_source => Ok(None),
+        // NOTE: replacing the target would delete the source after it is copied or moved
+        ConflictPolicy::Overwrite if is_nested => Err(AppError::InvalidTargetPath),
+        // NOTE: the operation writes into a free sibling, which replaces the target in finish
+        ConflictPolicy::Overwrite => Ok(Some(Target {
+            path: get_free_path(&target),
+            replaces: Some(target),
+        })),
+        ConflictPolicy::Rename => Ok(Some(Target {
+            path: get_free_path(&target),
+            replaces: None,
+        })),
+        ConflictPolicy::Skip => {
+            tracing::info!("skipping existing path {:?}", target);
+            Ok(None)
+        }
+    }
+}
+
+pub async fn finish(target: Target, result: Result<(), AppError>) -> Result<(), AppError> {
+    let replaced = match target.replaces {
+        Some(it) => it,
+        None => return result,
+    };
+
+    if let Err(error) = result {
+        if fs::symlink_metadata(&target.path).await.is_ok() {
+            if let Err(error) = remove(&target.path).await {
+                tracing::error!("removing incomplete path failed: {:?}", error);
+            }
+        }
+        return Err(error);
+    }
+
+    tracing::info!("overwriting existing path {:?}", replaced);
+
+    remove(&replaced).await?;
+    fs::rename(&target.path, &replaced).await?;
+
+    Ok(())
+}
+
+async fn remove(path: &Path) -> Result<(), AppError> {
+    if fs::symlink_metadata(path).await?.is_dir() {
+        fs::remove_dir_all(path).await?;
+    } else {
+        fs::remove_file(path).await?;
+    }
+    Ok(())
+}
+
+pub fn get_free_path(target: &Path) -> PathBuf {
+    let stem = target
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let extension = target
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut index = 1;
+    loop {
+        let path = target.with_file_name(format!("{} ({}){}", stem, index, extension));
+        if path.symlink_metadata().is_err() {
+            return path;
+        }
+        index += 1;
+    }
+}
+
+mod test {
+    #[test]
+    fn get_free_path_with_extension() {
+        use std::path::Path;
+
+        let path = super::get_free_path(Path::new("/yeet/not/existing/foo.txt"));
+        assert_eq!(Path::new("/yeet/not/existing/foo (1).txt"), path);
+    }
+
+    #[test]
+    fn get_free_path_without_extension() {
+        use std::path::Path;
+
+        let path = super::get_free_path(Path::new("/yeet/not/existing/.config"));
+        assert_eq!(Path::new("/yeet/not/existing/.config (1)"), path);
+    }
+
+    #[test]
+    fn get_free_path_skips_existing() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(root.join("foo.txt"), "").unwrap();
+        fs::write(root.join("foo (1).txt"), "").unwrap();
+
+        let path = super::get_free_path(&root.join("foo.txt"));
+
+        assert_eq!(root.join("foo (2).txt"), path);
+    }
+
+    #[tokio::test]
+    async fn resolve_overwrite_nested_paths() {
+        use std::fs;
+
+        use crate::task::ConflictPolicy;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("dir/sub/dir")).unwrap();
+        fs::write(root.join("dir/sub/file"), "").unwrap();
+
+        let source = root.join("dir/sub");
+        let target = root.join("dir");
+        let parent = super::resolve(Some(&source), target, &ConflictPolicy::Overwrite).await;
+
+        let source = root.join("dir");
+        let target = root.join("dir/sub/dir");
+        let child = super::resolve(Some(&source), target, &ConflictPolicy::Overwrite).await;
+
+        assert!(parent.is_err());
+        assert!(child.is_err());
+        assert!(root.join("dir/sub/file").exists());
+        assert!(root.join("dir/sub/dir").exists());
+    }
+
+    #[tokio::test]
+    async fn finish_overwrite_keeps_target_on_failure() {
+        use std::fs;
+
+        use crate::{error::AppError, task::ConflictPolicy};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(root.join("source"), "new").unwrap();
+        fs::write(root.join("target"), "old").unwrap();
+
+        let source = root.join("source");
+        let target = super::resolve(
+            Some(&source),
+            root.join("target"),
+            &ConflictPolicy::Overwrite,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        fs::write(&target.path, "ne").unwrap();
+
+        let result = super::finish(target, Err(AppError::InvalidTargetPath)).await;
+
+        assert!(result.is_err());
+        assert_eq!("old", fs::read_to_string(root.join("target")).unwrap());
+        assert!(!root.join("target (1)").exists());
+    }
+
+    #[tokio::test]
+    async fn finish_overwrite_replaces_target_on_success() {
+        use std::fs;
+
+        use crate::task::ConflictPolicy;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(root.join("source"), "new").unwrap();
+        fs::create_dir_all(root.join("target/sub")).unwrap();
+
+        let source = root.join("source");
+        let target = super::resolve(
+            Some(&source),
+            root.join("target"),
+            &ConflictPolicy::Overwrite,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        fs::copy(&source, &target.path).unwrap();
+
+        let result = super::finish(target, Ok(())).await;
+
+        assert!(result.is_ok());
+        assert_eq!("new", fs::read_to_string(root.join("target")).unwrap());
+        assert!(!root.join("target (1)").exists());
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    fs::Metadata,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use filetime::FileTime;
+use tokio::{fs, sync::mpsc::Sender};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    error::AppError,
+    event::{Envelope, Message},
+};
+
+use super::to_envelope;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+pub async fn copy(
+    sender: &Sender<Envelope>,
+    identifier: &str,
+    source: &Path,
+    target: &Path,
+    cancellation: &CancellationToken,
+) -> Result<(), AppError> {
+    if target.starts_with(source) {
+        return Err(AppError::InvalidTargetPath);
+    }
+
+    let entries = match collect_entries(source, target, cancellation).await? {
+        Some(it) => it,
+        None => return Ok(()),
+    };
+
+    let total = entries.len();
+    let mut last_progress = Instant::now();
+    let mut directories = Vec::new();
+    for (index, (source, target)) in entries.into_iter().enumerate() {
+        if cancellation.is_cancelled() {
+            break;
+        }
+
+        let metadata = fs::symlink_metadata(&source).await?;
+        if metadata.is_symlink() {
+            copy_symlink(&source, &target).await?;
+            set_times(&target, &metadata, true)?;
+        } else if metadata.is_dir() {
+            fs::create_dir(&target).await?;
+            directories.push((target, metadata));
+        } else {
+            tokio::select! {
+                _ = cancellation.cancelled() => break,
+                result = fs::copy(&source, &target) => { result?; },
+            }
+            set_times(&target, &metadata, false)?;
+        }
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL || index + 1 == total {
+            last_progress = Instant::now();
+            send_progress(sender, identifier, index + 1, total).await;
+        }
+    }
+
+    if cancellation.is_cancelled() {
+        tracing::info!("copy of {:?} cancelled, removing {:?}", source, target);
+
+        return remove_partial_copy(target).await;
+    }
+
+    // NOTE: deepest directories first, because adding content changes the mtime of the parent
+    for (target, metadata) in directories.into_iter().rev() {
+        fs::set_permissions(&target, metadata.permissions()).await?;
+        set_times(&target, &metadata, false)?;
+    }
+
+    Ok(())
+}
+
+async fn collect_entries(
+    source: &Path,
+    target: &Path,
+    cancellation: &CancellationToken,
+) -> Result<Option<Vec<(PathBuf, PathBuf)>>, AppError> {
+    let mut entries = Vec::new();
+    let mut stack = vec![(source.to_path_buf(), target.to_path_buf())];
+    while let Some((source, target)) = stack.pop() {
+        if cancellation.is_cancelled() {
+            return Ok(None);
+        }
+
+        let metadata = fs::symlink_metadata(&source).await?;
+        if metadata.is_dir() {
+            let mut children = Vec::new();
+            let mut read_dir = fs::read_dir(&source).await?;
+            while let Some(entry) = read_dir.next_entry().await? {
+                children.push((entry.path(), target.join(entry.file_name())));
+            }
+
+            children.sort();
+            stack.extend(children.into_iter().rev());
+        }
+
+        entries.push((source, target));
+    }
+
+    Ok(Some(entries))
+}
+
+#[cfg(unix)]
+async fn copy_symlink(source: &Path, target: &Path) -> Result<(), AppError> {
+    let link = fs::read_link(source).await?;
+    fs::symlink(link, target).await?;
+
+    Ok(())
+}
+
+#[cfg(windows)]
+async fn copy_symlink(source: &Path, target: &Path) -> Result<(), AppError> {
+    let link = fs::read_link(source).await?;
+    if fs::metadata(source).await.is_ok_and(|m| m.is_dir()) {
+        fs::symlink_dir(link, target).await?;
+    } else {
+        fs::symlink_file(link, target).await?;
+    }
+
+    Ok(())
+}
+
+fn set_times(target: &Path, metadata: &Metadata, is_symlink: bool) -> Result<(), AppError> {
+    let accessed = FileTime::from_last_access_time(metadata);
+    let modified = FileTime::from_last_modification_time(metadata);
+    if is_symlink {
+        filetime::set_symlink_file_times(target, accessed, modified)?;
+    } else {
+        filetime::set_file_times(target, accessed, modified)?;
+    }
+
+    Ok(())
+}
+
+async fn remove_partial_copy(target: &Path) -> Result<(), AppError> {
+    match fs::symlink_metadata(target).await {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(target).await?,
+        Ok(_) => fs::remove_file(target).await?,
+        Err(_) => {}
+    };
+
+    Ok(())
+}
+
+async fn send_progress(sender: &Sender<Envelope>, identifier: &str, done: usize, total: usize) {
+    let message = Message::TaskProgress(identifier.to_owned(), done, total);
+    if let Err(error) = sender.send(to_envelope(vec![message])).await {
+        tracing::error!("sending task progress failed: {:?}", error);
+    }
+}
+
+mod test {
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn copy_directory_recursive() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        use filetime::FileTime;
+        use tokio::sync::mpsc;
+        use tokio_util::sync::CancellationToken;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let source = root.join("source");
+        let target = root.join("target");
+
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/file.txt"), "content").unwrap();
+        fs::set_permissions(
+            source.join("nested/file.txt"),
+            fs::Permissions::from_mode(0o640),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink("nested/file.txt", source.join("link")).unwrap();
+
+        let mtime = FileTime::from_unix_time(1_000_000, 0);
+        filetime::set_file_mtime(source.join("nested/file.txt"), mtime).unwrap();
+        filetime::set_file_mtime(source.join("nested"), mtime).unwrap();
+
+        let (sender, mut receiver) = mpsc::channel(10);
+        let result = super::copy(&sender, "id", &source, &target, &CancellationToken::new()).await;
+
+        let copied = target.join("nested/file.txt");
+        let file_metadata = fs::metadata(&copied).unwrap();
+        let dir_metadata = fs::metadata(target.join("nested")).unwrap();
+        let link = fs::read_link(target.join("link")).unwrap();
+        let content = fs::read_to_string(&copied).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!("content", content);
+        assert_eq!(0o640, file_metadata.permissions().mode() & 0o777);
+        assert_eq!(mtime, FileTime::from_last_modification_time(&file_metadata));
+        assert_eq!(mtime, FileTime::from_last_modification_time(&dir_metadata));
+        assert_eq!(std::path::Path::new("nested/file.txt"), link);
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn copy_into_itself() {
+        use std::path::Path;
+
+        use tokio::sync::mpsc;
+        use tokio_util::sync::CancellationToken;
+
+        let (sender, _receiver) = mpsc::channel(1);
+        let result = super::copy(
+            &sender,
+            "id",
+            Path::new("/tmp/source"),
+            Path::new("/tmp/source/target"),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use ignore::WalkBuilder;
+use tokio::sync::mpsc::Sender;
+use tokio_util::sync::CancellationToken;
+
+use crate::event::{Envelope, Message};
+
+use super::{to_envelope, visibility::Visibility};
+
+const MAX_CACHE_SIZE: usize = 5000;
+
+pub async fn find(
+    sender: &Sender<Envelope>,
+    root: PathBuf,
+    visibility: Visibility,
+    cancellation: CancellationToken,
+) {
+    let sender = sender.clone();
+
+    // NOTE: the walker is blocking, thus it must not run on the async runtime threads
+    let result = tokio::task::spawn_blocking(move || {
+        let respect_ignore = visibility.respect_ignore;
+        let walker = WalkBuilder::new(&root)
+            .hidden(!visibility.show_hidden)
+            .ignore(respect_ignore)
+            .git_ignore(respect_ignore)
+            .git_exclude(respect_ignore)
+            .git_global(respect_ignore)
+            .parents(respect_ignore)
+            .require_git(false)
+            .build();
+
+        let mut cache = Vec::new();
+        let mut cache_size = 100;
+        for entry in walker {
+            if cancellation.is_cancelled() {
+                return;
+            }
+
+            let entry = match entry {
+                Ok(it) => it,
+                Err(err) => {
+                    tracing::debug!("walking path failed: {:?}", err);
+                    continue;
+                }
+            };
+
+            if let Ok(path) = entry.path().strip_prefix(&root) {
+                if !path.as_os_str().is_empty() {
+                    cache.push(path.to_path_buf());
+                }
+            }
+
+            if cache.len() >= cache_size {
+                let paths = std::mem::take(&mut cache);
+                let message = Message::FinderPathsAdded(root.clone(), paths);
+                if sender.blocking_send(to_envelope(vec![message])).is_err() {
+                    return;
+                }
+
+                cache_size = (cache_size * 2).min(MAX_CACHE_SIZE);
+            }
+        }
+
+        if !cache.is_empty() && !cancellation.is_cancelled() {
+            let message = Message::FinderPathsAdded(root, cache);
+            let _ = sender.blocking_send(to_envelope(vec![message]));
+        }
+    })
+    .await;
+
+    if let Err(err) = result {
+        tracing::error!("finding paths failed: {:?}", err);
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    str,
+};
+
+use tokio::process::Command;
+
+use crate::{error::AppError, model::git::GitStatus};
+
+pub async fn status(path: &Path) -> Result<(PathBuf, Vec<(PathBuf, GitStatus)>), AppError> {
+    tracing::debug!("executing git status at {:?}", path);
+
+    let root = execute(path, &["rev-parse", "--show-toplevel"]).await?;
+    let root = PathBuf::from(root.trim_end());
+
+    let output = execute(&root, &["status", "--porcelain=v1", "-z", "--ignored"]).await?;
+
+    Ok((root.clone(), get_status_entries(&root, &output)))
+}
+
+async fn execute(path: &Path, args: &[&str]) -> Result<String, AppError> {
+    let result = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await;
+
+    match result {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Ok(output) => Err(AppError::ExecutionFailed(format!(
+            "git failed: {:?}",
+            output
+        ))),
+        Err(err) => Err(AppError::ExecutionFailed(format!("git failed: {:?}", err))),
+    }
+}
+
+fn get_status_entries(root: &Path, output: &str) -> Vec<(PathBuf, GitStatus)> {
+    let mut entries = Vec::new();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        if field.len() < 4 {
+            continue;
+        }
+
+        let (code, path) = field.split_at(3);
+        let mut code = code.chars();
+        let index = code.next().unwrap_or(' ');
+        let worktree = code.next().unwrap_or(' ');
+
+        // NOTE: renames and copies are followed by their source path
+        if matches!(index, 'R' | 'C') {
+            fields.next();
+        }
+
+        let status = match (index, worktree) {
+            ('?', '?') => GitStatus::Untracked,
+            ('!', '!') => GitStatus::Ignored,
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => GitStatus::Conflicted,
+            (_, ' ') => GitStatus::Staged,
+            _ => GitStatus::Modified,
+        };
+
+        entries.push((root.join(path.trim_end_matches('/')), status));
+    }
+
+    entries
+}
+
+mod test {
+    #[test]
+    fn get_status_entries() {
+        use std::path::{Path, PathBuf};
+
+        use crate::model::git::GitStatus;
+
+        let output = " M src/main.rs\0M  README.md\0R  new.rs\0old.rs\0?? tmp/\0!! target/\0UU conflict.rs\0";
+
+        let result = super::get_status_entries(Path::new("/repo"), output);
+
+        assert_eq!(
+            result,
+            vec![
+                (PathBuf::from("/repo/src/main.rs"), GitStatus::Modified),
+                (PathBuf::from("/repo/README.md"), GitStatus::Staged),
+                (PathBuf::from("/repo/new.rs"), GitStatus::Staged),
+                (PathBuf::from("/repo/tmp"), GitStatus::Untracked),
+                (PathBuf::from("/repo/target"), GitStatus::Ignored),
+                (PathBuf::from("/repo/conflict.rs"), GitStatus::Conflicted),
+            ]
+        );
+    }
+}
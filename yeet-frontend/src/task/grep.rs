@@ -0,0 +1,145 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use ignore::WalkBuilder;
+use regex::Regex;
+use tokio_util::sync::CancellationToken;
+
+use super::visibility::Visibility;
+
+// NOTE: like git and ripgrep, a nul byte in the first chunk marks a file as binary
+const BINARY_DETECTION_SIZE: usize = 8000;
+
+pub async fn grep(
+    root: PathBuf,
+    regex: Regex,
+    visibility: Visibility,
+    cancellation: CancellationToken,
+) -> Vec<(PathBuf, usize, String)> {
+    let result = tokio::task::spawn_blocking(move || {
+        let respect_ignore = visibility.respect_ignore;
+        let walker = WalkBuilder::new(&root)
+            .hidden(!visibility.show_hidden)
+            .ignore(respect_ignore)
+            .git_ignore(respect_ignore)
+            .git_exclude(respect_ignore)
+            .git_global(respect_ignore)
+            .parents(respect_ignore)
+            .require_git(false)
+            .build();
+
+        let mut matches = Vec::new();
+        for entry in walker {
+            if cancellation.is_cancelled() {
+                return Vec::new();
+            }
+
+            let entry = match entry {
+                Ok(it) => it,
+                Err(err) => {
+                    tracing::debug!("walking path failed: {:?}", err);
+                    continue;
+                }
+            };
+
+            if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+                continue;
+            }
+
+            for (line_number, text) in find_matches(entry.path(), &regex) {
+                matches.push((entry.path().to_path_buf(), line_number, text));
+            }
+        }
+
+        matches
+    })
+    .await;
+
+    match result {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::error!("grep failed: {:?}", err);
+            Vec::new()
+        }
+    }
+}
+
+fn find_matches(path: &Path, regex: &Regex) -> Vec<(usize, String)> {
+    let file = match File::open(path) {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::debug!("opening file {:?} failed: {:?}", path, err);
+            return Vec::new();
+        }
+    };
+
+    let mut reader = BufReader::new(file);
+    if is_binary(&mut reader) {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return matches,
+            Ok(_) => line_number += 1,
+            Err(err) => {
+                tracing::debug!("reading file {:?} failed: {:?}", path, err);
+                return matches;
+            }
+        }
+
+        let content = String::from_utf8_lossy(&line);
+        let content = content.trim_end_matches(['\n', '\r']);
+        if regex.is_match(content) {
+            matches.push((line_number, content.trim().to_owned()));
+        }
+    }
+}
+
+fn is_binary<R: Read>(reader: &mut BufReader<R>) -> bool {
+    match reader.fill_buf() {
+        Ok(buffer) => {
+            let size = buffer.len().min(BINARY_DETECTION_SIZE);
+            buffer[..size].contains(&0)
+        }
+        Err(_) => true,
+    }
+}
+
+mod test {
+    #[test]
+    fn find_matches_skips_binary_files() {
+        use std::fs;
+
+        use regex::Regex;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join("text.txt"),
+            "first\n  second match\nthird match\n",
+        )
+        .unwrap();
+        fs::write(root.join("binary.bin"), b"match\0\x01\x02").unwrap();
+
+        let regex = Regex::new("match").unwrap();
+        let text = super::find_matches(&root.join("text.txt"), &regex);
+        let binary = super::find_matches(&root.join("binary.bin"), &regex);
+
+        assert_eq!(
+            vec![
+                (2, "second match".to_owned()),
+                (3, "third match".to_owned())
+            ],
+            text
+        );
+        assert!(binary.is_empty());
+    }
+}
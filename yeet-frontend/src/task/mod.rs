@@ -6,6 +6,7 @@ use std::{
 
 use ratatui::layout::Rect;
 use ratatui_image::picker::Picker;
+use regex::Regex;
 use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
 use tokio::{
     fs,
@@ -25,29 +26,47 @@ use crate::{
     error::AppError,
     event::{ContentKind, Envelope, Message, MessageSource},
     init::{
-        junkyard::{self, cache_and_compress, compress, restore},
+        journal::save_journal_to_file,
+        junkyard::{self, cache_and_compress, compress, restore, restore_as},
         mark::{load_marks_from_file, save_marks_to_file},
     },
-    model::{junkyard::FileEntry, mark::Marks},
+    model::{journal::Journal, junkyard::FileEntry, mark::Marks},
+    settings::Column,
 };
 
+use self::visibility::{Visibility, VisibilityFilter};
+
+pub mod archive;
+pub mod column;
 mod command;
+mod conflict;
+mod copy;
+mod finder;
+mod git;
+mod grep;
 mod image;
 mod syntax;
+pub mod visibility;
 
 pub enum Task {
     AddPath(PathBuf),
-    CopyPath(PathBuf, PathBuf),
+    CopyPath(PathBuf, PathBuf, ConflictPolicy),
     DeleteMarks(Vec<char>),
     DeletePath(PathBuf),
     DeleteJunkYardEntry(FileEntry),
     EmitMessages(Vec<Message>),
-    EnumerateDirectory(PathBuf, Option<String>),
+    EnumerateDirectory(PathBuf, Option<String>, Visibility, Vec<Column>),
     ExecuteFd(PathBuf, String),
+    ExecuteGrep(PathBuf, Regex, Visibility),
     ExecuteZoxide(String),
+    ExtractArchiveEntry(PathBuf, String, PathBuf),
+    FindPaths(PathBuf, Visibility),
+    LoadGitStatus(PathBuf),
     LoadPreview(PathBuf, Rect),
-    RenamePath(PathBuf, PathBuf),
-    RestorePath(FileEntry, PathBuf),
+    RenamePath(PathBuf, PathBuf, ConflictPolicy),
+    RenamePaths(Vec<(PathBuf, PathBuf)>),
+    RestorePath(FileEntry, PathBuf, ConflictPolicy),
+    SaveJournal(Journal),
     TrashPath(FileEntry),
     YankPath(FileEntry),
 }
@@ -56,17 +75,31 @@ impl Task {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Task::AddPath(path) => write!(f, "AddPath({:?})", path),
-            Task::CopyPath(src, dst) => write!(f, "CopyPath({:?}, {:?})", src, dst),
+            Task::CopyPath(src, dst, _) => write!(f, "CopyPath({:?}, {:?})", src, dst),
             Task::DeleteMarks(marks) => write!(f, "DeleteMarks({:?})", marks),
             Task::DeletePath(path) => write!(f, "DeletePath({:?})", path),
             Task::DeleteJunkYardEntry(entry) => write!(f, "DeleteJunkYardEntry({:?})", entry),
             Task::EmitMessages(_) => write!(f, "EmitMessages"),
-            Task::EnumerateDirectory(path, _) => write!(f, "EnumerateDirectory({:?}, _)", path),
+            Task::EnumerateDirectory(path, _, _, _) => {
+                write!(f, "EnumerateDirectory({:?}, _)", path)
+            }
             Task::ExecuteFd(base, params) => write!(f, "ExecuteFd({:?}, {:?})", base, params),
+            Task::ExecuteGrep(base, regex, _) => {
+                write!(f, "ExecuteGrep({:?}, {:?})", base, regex.as_str())
+            }
             Task::ExecuteZoxide(params) => write!(f, "ExecuteZoxide({:?})", params),
+            Task::ExtractArchiveEntry(path, entry, target) => write!(
+                f,
+                "ExtractArchiveEntry({:?}, {:?}, {:?})",
+                path, entry, target
+            ),
+            Task::FindPaths(path, _) => write!(f, "FindPaths({:?})", path),
+            Task::LoadGitStatus(path) => write!(f, "LoadGitStatus({:?})", path),
             Task::LoadPreview(path, rect) => write!(f, "LoadPreview({:?}, {})", path, rect),
-            Task::RenamePath(old, new) => write!(f, "RenamePath({:?}, {:?})", old, new),
-            Task::RestorePath(entry, path) => write!(f, "RestorePath({:?}, {:?})", entry, path),
+            Task::RenamePath(old, new, _) => write!(f, "RenamePath({:?}, {:?})", old, new),
+            Task::RenamePaths(renames) => write!(f, "RenamePaths({:?})", renames),
+            Task::RestorePath(entry, path, _) => write!(f, "RestorePath({:?}, {:?})", entry, path),
+            Task::SaveJournal(_) => write!(f, "SaveJournal"),
             Task::TrashPath(entry) => write!(f, "TrashPath({:?})", entry),
             Task::YankPath(entry) => write!(f, "YankPath({:?})", entry),
         }
@@ -91,16 +124,33 @@ impl PartialEq for Task {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Task::AddPath(p1), Task::AddPath(p2)) => p1 == p2,
-            (Task::CopyPath(s1, t1), Task::CopyPath(s2, t2)) => s1 == s2 && t1 == t2,
+            (Task::CopyPath(s1, t1, p1), Task::CopyPath(s2, t2, p2)) => {
+                s1 == s2 && t1 == t2 && p1 == p2
+            }
             (Task::DeleteMarks(m1), Task::DeleteMarks(m2)) => m1 == m2,
             (Task::DeletePath(p1), Task::DeletePath(p2)) => p1 == p2,
             (Task::DeleteJunkYardEntry(e1), Task::DeleteJunkYardEntry(e2)) => e1 == e2,
-            (Task::EnumerateDirectory(p1, s1), Task::EnumerateDirectory(p2, s2)) => {
-                p1 == p2 && s1 == s2
+            (
+                Task::EnumerateDirectory(p1, s1, v1, c1),
+                Task::EnumerateDirectory(p2, s2, v2, c2),
+            ) => p1 == p2 && s1 == s2 && v1 == v2 && c1 == c2,
+            (Task::ExecuteGrep(p1, r1, v1), Task::ExecuteGrep(p2, r2, v2)) => {
+                p1 == p2 && r1.as_str() == r2.as_str() && v1 == v2
+            }
+            (Task::ExtractArchiveEntry(p1, e1, t1), Task::ExtractArchiveEntry(p2, e2, t2)) => {
+                p1 == p2 && e1 == e2 && t1 == t2
             }
+            (Task::FindPaths(p1, v1), Task::FindPaths(p2, v2)) => p1 == p2 && v1 == v2,
+            (Task::LoadGitStatus(p1), Task::LoadGitStatus(p2)) => p1 == p2,
             (Task::LoadPreview(p1, r1), Task::LoadPreview(p2, r2)) => p1 == p2 && r1 == r2,
-            (Task::RenamePath(o1, n1), Task::RenamePath(o2, n2)) => o1 == o2 && n1 == n2,
-            (Task::RestorePath(e1, p1), Task::RestorePath(e2, p2)) => e1 == e2 && p1 == p2,
+            (Task::RenamePath(o1, n1, p1), Task::RenamePath(o2, n2, p2)) => {
+                o1 == o2 && n1 == n2 && p1 == p2
+            }
+            (Task::RenamePaths(r1), Task::RenamePaths(r2)) => r1 == r2,
+            (Task::RestorePath(e1, t1, p1), Task::RestorePath(e2, t2, p2)) => {
+                e1 == e2 && t1 == t2 && p1 == p2
+            }
+            (Task::SaveJournal(j1), Task::SaveJournal(j2)) => j1 == j2,
             (Task::TrashPath(e1), Task::TrashPath(e2)) => e1 == e2,
             (Task::YankPath(e1), Task::YankPath(e2)) => e1 == e2,
             _ => false,
@@ -108,6 +158,15 @@ impl PartialEq for Task {
     }
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ConflictPolicy {
+    #[default]
+    Fail,
+    Overwrite,
+    Rename,
+    Skip,
+}
+
 pub struct TaskManager {
     pub sender: mpsc::UnboundedSender<Task>,
 }
@@ -163,6 +222,7 @@ impl TaskManager {
                             ).await
                             {
                                 tracing::error!("handling task failed: {:?}", err);
+                                send_task_failed(&sender, id.as_str(), err).await;
                             };
 
                             send_task_finished(&sender, id.as_str()).await;
@@ -220,12 +280,22 @@ async fn run_task(
                 }
             }
         }
-        Task::CopyPath(source, target) => {
-            if !source.exists() || target.exists() {
+        Task::CopyPath(source, target, policy) => {
+            if !source.exists() {
                 return Err(AppError::InvalidTargetPath);
             }
 
-            fs::copy(source, target).await?;
+            let identifier =
+                Task::CopyPath(source.clone(), target.clone(), policy.clone()).to_string();
+
+            let target = match conflict::resolve(Some(&source), target, &policy).await? {
+                Some(it) => it,
+                None => return Ok(()),
+            };
+
+            let result =
+                copy::copy(sender, &identifier, &source, &target.path, &cancellation).await;
+            conflict::finish(target, result).await?;
         }
         Task::DeleteMarks(marks) => {
             let mut current = Marks::default();
@@ -290,11 +360,13 @@ async fn run_task(
                 emit_error(sender, AppError::ActionSendFailed(error)).await;
             }
         }
-        Task::EnumerateDirectory(path, selection) => {
+        Task::EnumerateDirectory(path, selection, visibility, columns) => {
             if !path.exists() {
                 return Err(AppError::InvalidTargetPath);
             }
 
+            let filter = VisibilityFilter::new(&visibility, &path);
+
             let read_dir = fs::read_dir(path.clone()).await;
             let mut cache = Vec::new();
             match read_dir {
@@ -304,14 +376,12 @@ async fn run_task(
                     let (is_selection, selection_path) = match &selection {
                         Some(selection) => {
                             let path = path.join(selection);
-                            if path.exists() {
-                                let kind = if path.is_dir() {
-                                    ContentKind::Directory
-                                } else {
-                                    ContentKind::File
-                                };
-
-                                cache.push((kind, selection.clone()));
+                            if path.exists() && filter.is_visible(&path, path.is_dir()) {
+                                cache.push((
+                                    ContentKind::from_path(&path),
+                                    selection.clone(),
+                                    column::get_formatted_columns(&columns, &path),
+                                ));
 
                                 (true, path)
                             } else {
@@ -327,19 +397,21 @@ async fn run_task(
                             break;
                         }
 
-                        let kind = if entry.path().is_dir() {
-                            ContentKind::Directory
-                        } else {
-                            ContentKind::File
-                        };
+                        let is_dir = entry.path().is_dir();
+                        if !filter.is_visible(&entry.path(), is_dir) {
+                            continue;
+                        }
 
+                        let kind = ContentKind::from_path(&entry.path());
                         let content = match entry.path().file_name() {
                             Some(content) => content.to_str().unwrap_or("").to_string(),
                             None => "".to_string(),
                         };
 
                         if !is_selection || entry.path() != selection_path {
-                            cache.push((kind, content));
+                            // NOTE: columns read metadata and user names, thus are formatted here
+                            let columns = column::get_formatted_columns(&columns, &entry.path());
+                            cache.push((kind, content, columns));
                         }
 
                         if cache.len() >= cache_size {
@@ -384,6 +456,49 @@ async fn run_task(
                 emit_error(sender, err).await;
             }
         },
+        Task::ExtractArchiveEntry(path, entry, target) => {
+            archive::extract(path, entry, target).await?
+        }
+        Task::FindPaths(path, visibility) => {
+            finder::find(sender, path, visibility, cancellation).await;
+        }
+        Task::LoadGitStatus(path) => match git::status(&path).await {
+            Ok((root, entries)) => {
+                let result = sender
+                    .send(to_envelope(vec![Message::GitStatusLoaded(root, entries)]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
+                }
+            }
+            Err(err) => {
+                // NOTE: paths outside of repositories or missing git binaries are no errors
+                tracing::debug!("git status for {:?} not available: {:?}", path, err);
+
+                let result = sender
+                    .send(to_envelope(vec![Message::GitStatusUnavailable(path)]))
+                    .await;
+
+                if let Err(error) = result {
+                    tracing::error!("sending message failed: {:?}", error);
+                }
+            }
+        },
+        Task::ExecuteGrep(base, regex, visibility) => {
+            let matches = grep::grep(base, regex, visibility, cancellation.clone()).await;
+            if cancellation.is_cancelled() {
+                return Ok(());
+            }
+
+            let result = sender
+                .send(to_envelope(vec![Message::GrepResult(matches)]))
+                .await;
+
+            if let Err(error) = result {
+                tracing::error!("sending message failed: {:?}", error);
+            }
+        }
         Task::ExecuteZoxide(params) => match command::zoxide(params).await {
             Ok(paths) => {
                 let result = sender
@@ -399,28 +514,33 @@ async fn run_task(
             }
         },
         Task::LoadPreview(path, rect) => {
-            let mime = if let Some(mime) = infer::get_from_path(&path)? {
-                let kind = mime.mime_type().split('/').collect::<Vec<_>>();
-                if kind.len() != 2 {
-                    return Err(AppError::InvalidMimeType);
-                }
-                Some(kind[0].to_ascii_lowercase())
+            // NOTE: archives are resolved by name, because infer only knows the outer gzip layer
+            let content = if let Some(preview) = archive::load(&path).await {
+                preview
             } else {
-                None
-            };
+                let mime = if let Some(mime) = infer::get_from_path(&path)? {
+                    let kind = mime.mime_type().split('/').collect::<Vec<_>>();
+                    if kind.len() != 2 {
+                        return Err(AppError::InvalidMimeType);
+                    }
+                    Some(kind[0].to_ascii_lowercase())
+                } else {
+                    None
+                };
 
-            let content = match mime.as_deref() {
-                Some("image") => {
-                    let mut picker = picker.lock().await;
+                match mime.as_deref() {
+                    Some("image") => {
+                        let mut picker = picker.lock().await;
 
-                    image::load(&mut picker, &path, &rect).await
-                }
-                _ => {
-                    let highlighter = highlighter.lock().await;
-                    let (syntaxes, theme_set) = (&highlighter.0, &highlighter.1);
-                    let theme = &theme_set.themes["base16-eighties.dark"];
+                        image::load(&mut picker, &path, &rect).await
+                    }
+                    _ => {
+                        let highlighter = highlighter.lock().await;
+                        let (syntaxes, theme_set) = (&highlighter.0, &highlighter.1);
+                        let theme = &theme_set.themes["base16-eighties.dark"];
 
-                    syntax::highlight(syntaxes, theme, &path).await
+                        syntax::highlight(syntaxes, theme, &path).await
+                    }
                 }
             };
 
@@ -432,15 +552,35 @@ async fn run_task(
                 tracing::error!("sending message failed: {:?}", error);
             }
         }
-        Task::RenamePath(old, new) => {
-            if !old.exists() || new.exists() {
-                return Err(AppError::InvalidTargetPath);
+        Task::RenamePath(old, new, policy) => rename(old, new, &policy).await?,
+        Task::RenamePaths(renames) => {
+            // NOTE: renames depend on each other (chains and cycles), thus order is important
+            for (old, new) in renames {
+                rename(old, new, &ConflictPolicy::Fail).await?;
             }
+        }
+        Task::RestorePath(entry, path, policy) => {
+            let file_name = match entry.target.file_name() {
+                Some(it) => it.to_owned(),
+                None => return Err(AppError::InvalidTargetPath),
+            };
+
+            let target = match conflict::resolve(None, path.join(&file_name), &policy).await? {
+                Some(it) => it,
+                None => return Ok(()),
+            };
 
-            fs::rename(old, new).await?;
+            let result = if target.path.file_name() == Some(&file_name) {
+                restore(entry, path)
+            } else {
+                restore_as(entry, target.path.clone())
+            };
+            conflict::finish(target, result).await?;
         }
-        Task::RestorePath(entry, path) => {
-            restore(entry, path)?;
+        Task::SaveJournal(journal) => {
+            if let Err(error) = save_journal_to_file(&journal) {
+                emit_error(sender, error).await;
+            }
         }
         Task::TrashPath(entry) => {
             if let Err(error) = cache_and_compress(entry).await {
@@ -457,6 +597,27 @@ async fn run_task(
     Ok(())
 }
 
+async fn rename(old: PathBuf, new: PathBuf, policy: &ConflictPolicy) -> Result<(), AppError> {
+    if !old.exists() {
+        return Err(AppError::InvalidTargetPath);
+    }
+
+    if old == new {
+        return Ok(());
+    }
+
+    if let Some(target) = conflict::resolve(Some(&old), new, policy).await? {
+        if let Some(parent) = target.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let result = fs::rename(old, &target.path).await.map_err(AppError::from);
+        conflict::finish(target, result).await?;
+    }
+
+    Ok(())
+}
+
 async fn send_task_started(
     sender: &Sender<Envelope>,
     identifier: &str,
@@ -494,6 +655,23 @@ async fn send_task_finished(sender: &Sender<Envelope>, identifier: &str) {
     };
 }
 
+async fn send_task_failed(sender: &Sender<Envelope>, identifier: &str, error: AppError) {
+    let reason = match error {
+        AppError::FileOperationFailed(err) => err.to_string(),
+        error => error.to_string(),
+    };
+
+    if let Err(err) = sender
+        .send(to_envelope(vec![Message::TaskFailed(
+            identifier.to_owned(),
+            reason,
+        )]))
+        .await
+    {
+        tracing::error!("task failed send failed: {:?}", err);
+    };
+}
+
 async fn emit_error(sender: &Sender<Envelope>, error: AppError) {
     tracing::error!("task failed: {:?}", error);
 
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use ignore::{gitignore::Gitignore, Match};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Visibility {
+    pub show_hidden: bool,
+    pub respect_ignore: bool,
+}
+
+pub struct VisibilityFilter {
+    show_hidden: bool,
+    ignores: Vec<Gitignore>,
+}
+
+impl VisibilityFilter {
+    pub fn new(visibility: &Visibility, directory: &Path) -> Self {
+        let ignores = if visibility.respect_ignore {
+            get_ignores(directory)
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            show_hidden: visibility.show_hidden,
+            ignores,
+        }
+    }
+
+    pub fn is_visible(&self, path: &Path, is_dir: bool) -> bool {
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+        if !self.show_hidden && is_hidden {
+            return false;
+        }
+
+        // NOTE: ignores are ordered from the deepest directory upwards, the first match wins
+        for ignore in &self.ignores {
+            match ignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return false,
+                Match::Whitelist(_) => return true,
+                Match::None => {}
+            }
+        }
+
+        true
+    }
+}
+
+fn get_ignores(directory: &Path) -> Vec<Gitignore> {
+    let mut ignores = Vec::new();
+    for ancestor in directory.ancestors() {
+        // NOTE: like ripgrep, .ignore takes precedence over .gitignore
+        for name in [".ignore", ".gitignore"] {
+            let file = ancestor.join(name);
+            if !file.is_file() {
+                continue;
+            }
+
+            let (gitignore, error) = Gitignore::new(&file);
+            if let Some(error) = error {
+                tracing::warn!("reading ignore file {:?} failed: {:?}", file, error);
+            }
+            ignores.push(gitignore);
+        }
+
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+
+    ignores
+}
+
+mod test {
+    #[test]
+    fn is_visible_hidden() {
+        use std::path::Path;
+
+        let visibility = super::Visibility {
+            show_hidden: false,
+            respect_ignore: false,
+        };
+        let filter = super::VisibilityFilter::new(&visibility, Path::new("/yeet"));
+
+        assert!(!filter.is_visible(Path::new("/yeet/.config"), true));
+        assert!(filter.is_visible(Path::new("/yeet/config"), true));
+    }
+
+    #[test]
+    fn is_visible_with_gitignore() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::write(root.join("src/.ignore"), "!keep.log\n").unwrap();
+
+        let visibility = super::Visibility {
+            show_hidden: true,
+            respect_ignore: true,
+        };
+        let filter = super::VisibilityFilter::new(&visibility, root);
+        let src_filter = super::VisibilityFilter::new(&visibility, &root.join("src"));
+
+        let target = filter.is_visible(&root.join("target"), true);
+        let log = filter.is_visible(&root.join("debug.log"), false);
+        let cargo = filter.is_visible(&root.join("Cargo.toml"), false);
+        let keep = src_filter.is_visible(&root.join("src/keep.log"), false);
+        let other = src_filter.is_visible(&root.join("src/other.log"), false);
+
+        assert!(!target);
+        assert!(!log);
+        assert!(cargo);
+        assert!(keep);
+        assert!(!other);
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::{
+    message::BufferMessage,
+    model::{BufferLine, Mode},
+    update::update_buffer,
+};
+use yeet_keymap::message::KeymapMessage;
+
+use crate::{
+    action::{self, Action},
+    event::Message,
+    model::{BufferType, BulkRename, Model},
+    task::{ConflictPolicy, Task},
+};
+
+use super::{journal, save::get_unsaved_changes_error};
+
+pub fn open(model: &mut Model, paths: Vec<PathBuf>) -> Vec<Action> {
+    if model.files.bulk_rename.is_some() {
+        return vec![action::emit_error("bulk rename is already in progress")];
+    }
+
+    if paths.is_empty() {
+        return vec![action::emit_error(
+            "no paths to rename, add paths to qfix or select them in visual mode",
+        )];
+    }
+
+    if let Some(error) = get_unsaved_changes_error(&model.files) {
+        return vec![error];
+    }
+
+    let buffer = &mut model.files.current.buffer;
+    let root = model.files.current.path.clone();
+    let content = paths
+        .iter()
+        .map(|path| BufferLine::from(&get_relative_path(&root, path)))
+        .collect();
+
+    update_buffer(
+        &mut model.files.current_vp,
+        &mut model.files.current_cursor,
+        &model.mode,
+        buffer,
+        &BufferMessage::SetContent(content),
+    );
+
+    update_buffer(
+        &mut model.files.current_vp,
+        &mut model.files.current_cursor,
+        &model.mode,
+        buffer,
+        &BufferMessage::ResetCursor,
+    );
+
+    model.files.bulk_rename = Some(BulkRename { paths, root });
+    model.files.preview = BufferType::None;
+
+    Vec::new()
+}
+
+pub fn discard(model: &mut Model) -> bool {
+    if model.files.bulk_rename.take().is_none() {
+        return false;
+    }
+
+    model.files.current.buffer.undo.save();
+
+    true
+}
+
+#[tracing::instrument(skip(model))]
+pub fn persist(model: &mut Model) -> Vec<Action> {
+    let bulk_rename = match model.files.bulk_rename.take() {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    // NOTE: relative lines resolve against the directory the bulk rename was opened in
+    let root = bulk_rename.root.clone();
+    let lines: Vec<_> = model
+        .files
+        .current
+        .buffer
+        .lines
+        .iter()
+        .map(|line| line.content.to_stripped_string())
+        .collect();
+
+    let tasks = get_renames(&root, &bulk_rename.paths, &lines).and_then(get_rename_tasks);
+    let tasks = match tasks {
+        Ok(it) => it,
+        Err(err) => {
+            model.files.bulk_rename = Some(bulk_rename);
+
+            // NOTE: stay in normal to enable fixing the renames without leaving the buffer
+            return vec![Action::EmitMessages(vec![
+                Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+                    model.mode.clone(),
+                    Mode::Normal,
+                ))),
+                Message::Error(err),
+            ])];
+        }
+    };
+
+    model.files.current.buffer.undo.save();
+
+    let mut actions: Vec<_> = tasks.into_iter().map(Action::Task).collect();
+    let journal_actions = journal::add_group(&mut model.journal, &actions);
+    actions.extend(journal_actions);
+
+    actions.push(action::emit_keymap(KeymapMessage::NavigateToPath(root)));
+
+    actions
+}
+
+fn get_relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn get_renames(
+    root: &Path,
+    paths: &[PathBuf],
+    lines: &[String],
+) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let mut lines = lines.to_vec();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    if lines.len() != paths.len() {
+        return Err(format!(
+            "bulk rename expects {} lines, but got {}. Lines must not be added or removed",
+            paths.len(),
+            lines.len()
+        ));
+    }
+
+    let mut renames = Vec::new();
+    for (path, line) in paths.iter().zip(lines) {
+        if line.trim().is_empty() {
+            return Err(format!("bulk rename target for {:?} is empty", path));
+        }
+
+        let target = root.join(line);
+        if path != &target {
+            renames.push((path.to_path_buf(), target));
+        }
+    }
+
+    Ok(renames)
+}
+
+fn get_rename_tasks(renames: Vec<(PathBuf, PathBuf)>) -> Result<Vec<Task>, String> {
+    let sources: HashMap<_, _> = renames.iter().map(|(old, new)| (old, new)).collect();
+
+    let mut targets = HashSet::new();
+    for (_, new) in &renames {
+        if !targets.insert(new) {
+            return Err(format!(
+                "bulk rename target {:?} is used more than once",
+                new
+            ));
+        }
+
+        if !sources.contains_key(new) && new.symlink_metadata().is_ok() {
+            return Err(format!("bulk rename target {:?} already exists", new));
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut tasks = Vec::new();
+
+    // NOTE: chains (a->b, b->c) must rename the last link first to free up the targets
+    for (old, _) in &renames {
+        if targets.contains(old) {
+            continue;
+        }
+
+        let mut chain = Vec::new();
+        let mut current = old;
+        while let Some(new) = sources.get(current) {
+            visited.insert(current);
+            chain.push((current.to_path_buf(), new.to_path_buf()));
+            current = new;
+        }
+
+        chain.reverse();
+        tasks.push(to_task(chain));
+    }
+
+    // NOTE: cycles (a->b, b->a) have no free target, thus one path is moved to a temporary name
+    for (old, _) in &renames {
+        if visited.contains(old) {
+            continue;
+        }
+
+        let mut cycle = Vec::new();
+        let mut current = old;
+        loop {
+            visited.insert(current);
+
+            let new = sources[current];
+            cycle.push((current.to_path_buf(), new.to_path_buf()));
+
+            current = new;
+            if current == old {
+                break;
+            }
+        }
+
+        let temporary = get_temporary_path(old);
+        let (first_old, first_new) = cycle.remove(0);
+
+        let mut steps = vec![(first_old, temporary.clone())];
+        steps.extend(cycle.into_iter().rev());
+        steps.push((temporary, first_new));
+
+        tasks.push(Task::RenamePaths(steps));
+    }
+
+    Ok(tasks)
+}
+
+fn to_task(mut renames: Vec<(PathBuf, PathBuf)>) -> Task {
+    if renames.len() == 1 {
+        let (old, new) = renames.remove(0);
+        Task::RenamePath(old, new, ConflictPolicy::Fail)
+    } else {
+        Task::RenamePaths(renames)
+    }
+}
+
+fn get_temporary_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut index = 0;
+    loop {
+        let temporary = path.with_file_name(format!(".{}.yeet-rename-{}", name, index));
+        if temporary.symlink_metadata().is_err() {
+            return temporary;
+        }
+        index += 1;
+    }
+}
+
+mod test {
+    #[test]
+    fn get_renames_relative_and_absolute() {
+        use std::path::{Path, PathBuf};
+
+        let root = Path::new("/yeet/root");
+        let paths = vec![
+            PathBuf::from("/yeet/root/a.txt"),
+            PathBuf::from("/yeet/root/b.txt"),
+            PathBuf::from("/yeet/other/c.txt"),
+        ];
+        let lines = vec![
+            "sub/a.txt".to_owned(),
+            "b.txt".to_owned(),
+            "/yeet/root/c.txt".to_owned(),
+            "".to_owned(),
+        ];
+
+        let result = super::get_renames(root, &paths, &lines).unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    PathBuf::from("/yeet/root/a.txt"),
+                    PathBuf::from("/yeet/root/sub/a.txt")
+                ),
+                (
+                    PathBuf::from("/yeet/other/c.txt"),
+                    PathBuf::from("/yeet/root/c.txt")
+                ),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn get_renames_with_removed_line() {
+        use std::path::{Path, PathBuf};
+
+        let paths = vec![PathBuf::from("/yeet/a"), PathBuf::from("/yeet/b")];
+        let lines = vec!["a".to_owned()];
+
+        let result = super::get_renames(Path::new("/yeet"), &paths, &lines);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn get_rename_tasks_chain() {
+        use std::path::PathBuf;
+
+        use crate::task::{ConflictPolicy, Task};
+
+        let renames = vec![
+            (PathBuf::from("/yeet/a"), PathBuf::from("/yeet/b")),
+            (PathBuf::from("/yeet/b"), PathBuf::from("/yeet/c")),
+            (PathBuf::from("/yeet/d"), PathBuf::from("/yeet/e")),
+        ];
+
+        let result = super::get_rename_tasks(renames).unwrap();
+
+        assert_eq!(
+            vec![
+                Task::RenamePaths(vec![
+                    (PathBuf::from("/yeet/b"), PathBuf::from("/yeet/c")),
+                    (PathBuf::from("/yeet/a"), PathBuf::from("/yeet/b")),
+                ]),
+                Task::RenamePath(
+                    PathBuf::from("/yeet/d"),
+                    PathBuf::from("/yeet/e"),
+                    ConflictPolicy::Fail
+                ),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn get_rename_tasks_swap() {
+        use std::path::PathBuf;
+
+        use crate::task::Task;
+
+        let renames = vec![
+            (PathBuf::from("/yeet/a"), PathBuf::from("/yeet/b")),
+            (PathBuf::from("/yeet/b"), PathBuf::from("/yeet/a")),
+        ];
+
+        let result = super::get_rename_tasks(renames).unwrap();
+
+        let temporary = PathBuf::from("/yeet/.a.yeet-rename-0");
+        assert_eq!(
+            vec![Task::RenamePaths(vec![
+                (PathBuf::from("/yeet/a"), temporary.clone()),
+                (PathBuf::from("/yeet/b"), PathBuf::from("/yeet/a")),
+                (temporary, PathBuf::from("/yeet/b")),
+            ])],
+            result
+        );
+    }
+
+    #[test]
+    fn get_rename_tasks_duplicate_target() {
+        use std::path::PathBuf;
+
+        let renames = vec![
+            (PathBuf::from("/yeet/a"), PathBuf::from("/yeet/c")),
+            (PathBuf::from("/yeet/b"), PathBuf::from("/yeet/c")),
+        ];
+
+        let result = super::get_rename_tasks(renames);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::model::{mark::Marks, CurrentTask, Model};
+
+use super::{COMMANDS, VISUAL_RANGE};
+
+pub fn get_completion(model: &Model, line: &str) -> (String, Vec<String>) {
+    let (range, cmd) = match line.strip_prefix(VISUAL_RANGE) {
+        Some(it) => (VISUAL_RANGE, it),
+        None => ("", line),
+    };
+
+    let (base, token) = match cmd.rsplit_once(' ') {
+        Some((base, token)) => (format!("{}{} ", range, base), token),
+        None => return (range.to_owned(), get_command_candidates(cmd)),
+    };
+
+    let candidates = match cmd.split_once(' ') {
+        Some(("delt", _)) => get_task_candidates(&model.current_tasks, token),
+        _ if token.starts_with('\'') => get_mark_candidates(&model.marks, token),
+        _ => get_path_candidates(&model.files.current.path, token),
+    };
+
+    (base, candidates)
+}
+
+fn get_command_candidates(token: &str) -> Vec<String> {
+    COMMANDS
+        .iter()
+        .filter(|cmd| cmd.starts_with(token))
+        .map(|cmd| cmd.to_string())
+        .collect()
+}
+
+fn get_task_candidates(tasks: &HashMap<String, CurrentTask>, token: &str) -> Vec<String> {
+    let mut ids: Vec<_> = tasks.values().map(|task| task.id).collect();
+    ids.sort();
+
+    ids.into_iter()
+        .map(|id| id.to_string())
+        .filter(|id| id.starts_with(token))
+        .collect()
+}
+
+fn get_mark_candidates(marks: &Marks, token: &str) -> Vec<String> {
+    let mut marks: Vec<_> = marks.entries.keys().collect();
+    marks.sort();
+
+    marks
+        .into_iter()
+        .map(|mark| format!("'{}", mark))
+        .filter(|mark| mark.starts_with(token))
+        .collect()
+}
+
+fn get_path_candidates(current: &Path, token: &str) -> Vec<String> {
+    let (directory, name) = match token.rsplit_once('/') {
+        Some((directory, name)) => (format!("{}/", directory), name),
+        None => (String::new(), token),
+    };
+
+    let path = match directory.strip_prefix("~/") {
+        Some(relative) => match dirs::home_dir() {
+            Some(home) => home.join(relative),
+            None => return Vec::new(),
+        },
+        None => current.join(&directory),
+    };
+
+    let entries = match fs::read_dir(&path) {
+        Ok(it) => it,
+        Err(err) => {
+            tracing::debug!("reading directory {:?} failed: {:?}", path, err);
+            return Vec::new();
+        }
+    };
+
+    let mut candidates: Vec<_> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let is_hidden = file_name.starts_with('.') && !name.starts_with('.');
+            if is_hidden || !file_name.starts_with(name) {
+                return None;
+            }
+
+            let separator = if is_directory(&entry.path()) { "/" } else { "" };
+            Some(format!("{}{}{}", directory, file_name, separator))
+        })
+        .collect();
+
+    candidates.sort();
+    candidates
+}
+
+fn is_directory(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.is_dir())
+}
+
+mod test {
+    #[test]
+    fn get_command_candidates() {
+        let result = super::get_command_candidates("cl");
+
+        assert_eq!(vec!["cl".to_owned(), "clearcl".to_owned()], result);
+    }
+
+    #[test]
+    fn get_mark_candidates() {
+        use std::path::PathBuf;
+
+        use crate::model::mark::Marks;
+
+        let mut marks = Marks::default();
+        marks.entries.insert('b', PathBuf::from("/yeet/b"));
+        marks.entries.insert('a', PathBuf::from("/yeet/a"));
+
+        let result = super::get_mark_candidates(&marks, "'");
+
+        assert_eq!(vec!["'a".to_owned(), "'b".to_owned()], result);
+    }
+
+    #[test]
+    fn get_path_candidates() {
+        use std::fs;
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/sub")).unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/.hidden"), "").unwrap();
+
+        let result = super::get_path_candidates(root, "src/");
+        let nested = super::get_path_candidates(&root.join("src"), "../src/m");
+
+        assert_eq!(
+            vec!["src/main.rs".to_owned(), "src/sub/".to_owned()],
+            result
+        );
+        assert_eq!(vec!["../src/main.rs".to_owned()], nested);
+    }
+}
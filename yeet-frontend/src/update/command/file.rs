@@ -6,15 +6,28 @@ use crate::{
     action::{self, Action},
     event::Message,
     model::{mark::Marks, Model},
-    task::Task,
+    task::{ConflictPolicy, Task},
 };
 
-pub fn copy(model: &Model, target: &str) -> Vec<Action> {
+pub fn copy(model: &Model, args: &str, force: bool) -> Vec<Action> {
+    copy_paths(model, &get_preview_path(model), args, force)
+}
+
+pub fn copy_paths(model: &Model, paths: &[PathBuf], args: &str, force: bool) -> Vec<Action> {
+    let (policy, target) = match get_conflict_policy(args, force) {
+        Ok(it) => it,
+        Err(err) => return vec![Action::EmitMessages(vec![Message::Error(err)])],
+    };
+
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in paths {
         tracing::info!("copying path: {:?}", path);
-        match get_target_file_path(&model.marks, target, path) {
-            Ok(target) => actions.push(Action::Task(Task::CopyPath(path.to_path_buf(), target))),
+        match get_target_file_path(&model.marks, target, path, &policy) {
+            Ok(target) => actions.push(Action::Task(Task::CopyPath(
+                path.to_path_buf(),
+                target,
+                policy.clone(),
+            ))),
             Err(err) => {
                 actions.push(Action::EmitMessages(vec![Message::Error(err)]));
             }
@@ -24,24 +37,44 @@ pub fn copy(model: &Model, target: &str) -> Vec<Action> {
 }
 
 pub fn delete_selection(model: &Model) -> Vec<Action> {
+    let paths = get_preview_path(model);
+    if paths.is_empty() {
+        tracing::warn!("deleting path failed: no path in preview set");
+    }
+
+    delete_paths(&paths)
+}
+
+pub fn delete_paths(paths: &[PathBuf]) -> Vec<Action> {
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in paths {
         tracing::info!("deleting path: {:?}", path);
         actions.push(Action::Task(Task::DeletePath(path.to_path_buf())));
-    } else {
-        tracing::warn!("deleting path failed: no path in preview set");
     }
 
     actions
 }
 
-pub fn rename_selection(model: &Model, target: &str) -> Vec<Action> {
+pub fn rename_selection(model: &Model, args: &str, force: bool) -> Vec<Action> {
+    rename_paths(model, &get_preview_path(model), args, force)
+}
+
+pub fn rename_paths(model: &Model, paths: &[PathBuf], args: &str, force: bool) -> Vec<Action> {
+    let (policy, target) = match get_conflict_policy(args, force) {
+        Ok(it) => it,
+        Err(err) => return vec![Action::EmitMessages(vec![Message::Error(err)])],
+    };
+
     let mut actions = Vec::new();
-    if let Some(path) = &model.files.preview.resolve_path() {
+    for path in paths {
         tracing::info!("renaming path: {:?}", path);
-        match get_target_file_path(&model.marks, target, path) {
+        match get_target_file_path(&model.marks, target, path, &policy) {
             Ok(target) => {
-                actions.push(Action::Task(Task::RenamePath(path.to_path_buf(), target)));
+                actions.push(Action::Task(Task::RenamePath(
+                    path.to_path_buf(),
+                    target,
+                    policy.clone(),
+                )));
             }
             Err(err) => {
                 actions.push(Action::EmitMessages(vec![Message::Error(err)]));
@@ -52,6 +85,15 @@ pub fn rename_selection(model: &Model, target: &str) -> Vec<Action> {
     actions
 }
 
+fn get_preview_path(model: &Model) -> Vec<PathBuf> {
+    model
+        .files
+        .preview
+        .resolve_path()
+        .map(|path| vec![path.to_path_buf()])
+        .unwrap_or_default()
+}
+
 pub fn refresh(model: &Model) -> Vec<Action> {
     let navigation = if let Some(path) = &model.files.preview.resolve_path() {
         KeymapMessage::NavigateToPathAsPreview(path.to_path_buf())
@@ -62,7 +104,27 @@ pub fn refresh(model: &Model) -> Vec<Action> {
     vec![action::emit_keymap(navigation)]
 }
 
-fn get_target_file_path(marks: &Marks, target: &str, path: &Path) -> Result<PathBuf, String> {
+fn get_conflict_policy(args: &str, force: bool) -> Result<(ConflictPolicy, &str), String> {
+    let (policy, target) = match args.split_once(' ') {
+        Some(("-a", target)) => (ConflictPolicy::Rename, target.trim()),
+        Some(("-n", target)) => (ConflictPolicy::Skip, target.trim()),
+        _ if force => return Ok((ConflictPolicy::Overwrite, args)),
+        _ => return Ok((ConflictPolicy::Fail, args)),
+    };
+
+    if force {
+        Err("'!' can not be combined with '-a' or '-n'".to_string())
+    } else {
+        Ok((policy, target))
+    }
+}
+
+fn get_target_file_path(
+    marks: &Marks,
+    target: &str,
+    path: &Path,
+    policy: &ConflictPolicy,
+) -> Result<PathBuf, String> {
     let file_name = match path.file_name() {
         Some(it) => it,
         None => return Err(format!("could not resolve file name from path {:?}", path)),
@@ -79,22 +141,44 @@ fn get_target_file_path(marks: &Marks, target: &str, path: &Path) -> Result<Path
         } else {
             return Err(format!("mark '{}' not found", mark));
         }
-    } else if path.is_relative() {
+    } else if Path::new(target).is_relative() {
         let current = match path.parent() {
             Some(it) => it,
             None => return Err(format!("could not resolve parent from path {:?}", path)),
         };
 
-        let path = Path::new(path);
-        current.join(path)
+        current.join(target)
     } else {
-        PathBuf::from(path)
+        PathBuf::from(target)
     };
 
     let target_file = target.join(file_name);
-    if target.is_dir() && target.exists() && !target_file.exists() {
+    let is_conflict = target_file.exists() && policy == &ConflictPolicy::Fail;
+    if target.is_dir() && target.exists() && !is_conflict {
         Ok(target.join(file_name))
     } else {
         Err("target path is not valid".to_string())
     }
 }
+
+mod test {
+    #[test]
+    fn get_conflict_policy_from_args() {
+        use crate::task::ConflictPolicy;
+
+        let result = super::get_conflict_policy("/some/path", false);
+        assert_eq!(Ok((ConflictPolicy::Fail, "/some/path")), result);
+
+        let result = super::get_conflict_policy("/some/path", true);
+        assert_eq!(Ok((ConflictPolicy::Overwrite, "/some/path")), result);
+
+        let result = super::get_conflict_policy("-a /some/path", false);
+        assert_eq!(Ok((ConflictPolicy::Rename, "/some/path")), result);
+
+        let result = super::get_conflict_policy("-n 'a", false);
+        assert_eq!(Ok((ConflictPolicy::Skip, "'a")), result);
+
+        let result = super::get_conflict_policy("-n /some/path", true);
+        assert!(result.is_err());
+    }
+}
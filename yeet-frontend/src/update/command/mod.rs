@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use yeet_buffer::{message::BufferMessage, model::Mode};
 use yeet_keymap::message::{KeymapMessage, QuitMode};
 
@@ -6,15 +8,62 @@ use crate::{
     event::Message,
     model::Model,
     task::Task,
+    update::{bulkrename, filter, finder, journal, sort},
 };
 
+pub mod completion;
 mod file;
 mod print;
 mod qfix;
+mod set;
 mod task;
 
+pub const VISUAL_RANGE: &str = "'<,'>";
+
+// NOTE: used for completion, thus must match the commands handled in execute
+pub const COMMANDS: [&str; 34] = [
+    "bulkrename",
+    "cdo",
+    "cfirst",
+    "cl",
+    "clearcl",
+    "cn",
+    "cN",
+    "cp",
+    "cp!",
+    "d!",
+    "delm",
+    "delt",
+    "e!",
+    "fd",
+    "filter",
+    "find",
+    "grep",
+    "invertcl",
+    "junk",
+    "marks",
+    "mv",
+    "mv!",
+    "noh",
+    "q",
+    "q!",
+    "reg",
+    "set",
+    "sort",
+    "sort!",
+    "tl",
+    "undo-ops",
+    "w",
+    "wq",
+    "z",
+];
+
 #[tracing::instrument(skip(model))]
 pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
+    if let Some(cmd) = cmd.strip_prefix(VISUAL_RANGE) {
+        return execute_on_visual_selection(cmd, model);
+    }
+
     let cmd_with_args = match cmd.split_once(' ') {
         Some(it) => it,
         None => (cmd, ""),
@@ -27,6 +76,10 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
 
     // NOTE: all file commands like e.g. d! should use preview path as target to enable cdo
     match cmd_with_args {
+        ("bulkrename", "") => {
+            let paths = model.qfix.entries.clone();
+            open_bulkrename(model, mode_before, mode, paths)
+        }
         ("cdo", command) => add_change_mode(mode_before, mode, qfix::cdo(model, command)),
         ("cfirst", "") => add_change_mode(mode_before, mode, qfix::select_first(model)),
         ("cl", "") => print::qfix(&model.qfix),
@@ -34,7 +87,8 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
         ("clearcl", path) => add_change_mode(mode_before, mode, qfix::clear_in(model, path)),
         ("cn", "") => add_change_mode(mode_before, mode, qfix::next(model)),
         ("cN", "") => add_change_mode(mode_before, mode, qfix::previous(model)),
-        ("cp", target) => add_change_mode(mode_before, mode, file::copy(model, target)),
+        ("cp", args) => add_change_mode(mode_before, mode, file::copy(model, args, false)),
+        ("cp!", args) => add_change_mode(mode_before, mode, file::copy(model, args, true)),
         ("d!", "") => add_change_mode(mode_before, mode, file::delete_selection(model)),
         ("delm", args) if !args.is_empty() => {
             let mut marks = Vec::new();
@@ -59,6 +113,14 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
 
             add_change_mode(mode_before, mode, actions)
         }
+        ("e!", "") if bulkrename::discard(model) => {
+            let path = model.files.current.path.clone();
+            add_change_mode(
+                mode_before,
+                Mode::Navigation,
+                vec![action::emit_keymap(KeymapMessage::NavigateToPath(path))],
+            )
+        }
         ("e!", "") => add_change_mode(mode_before, mode, file::refresh(model)),
         ("fd", params) => add_change_mode(
             mode_before,
@@ -68,10 +130,22 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
                 params.to_owned(),
             ))],
         ),
+        ("filter", pattern) => {
+            add_change_mode(mode_before, mode, filter::set_filter(model, pattern))
+        }
+        ("find", "") => finder::open(model),
+        ("grep", pattern) => add_change_mode(mode_before, mode, qfix::grep(model, pattern)),
         ("invertcl", "") => add_change_mode(mode_before, mode, qfix::invert_in_current(model)),
         ("junk", "") => print::junkyard(&model.junk),
         ("marks", "") => print::marks(&model.marks),
-        ("mv", target) => add_change_mode(mode_before, mode, file::rename_selection(model, target)),
+        ("mv", args) => add_change_mode(
+            mode_before,
+            mode,
+            file::rename_selection(model, args, false),
+        ),
+        ("mv!", args) => {
+            add_change_mode(mode_before, mode, file::rename_selection(model, args, true))
+        }
         ("noh", "") => add_change_mode(
             mode_before,
             mode,
@@ -84,7 +158,11 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
         ))],
         ("q!", "") => vec![action::emit_keymap(KeymapMessage::Quit(QuitMode::Force))],
         ("reg", "") => print::register(&model.register),
-        ("tl", "") => print::tasks(&model.current_tasks),
+        ("set", args) => add_change_mode(mode_before, mode, set::set(model, args)),
+        ("sort", args) => add_change_mode(mode_before, mode, sort::set_sort(model, args, false)),
+        ("sort!", args) => add_change_mode(mode_before, mode, sort::set_sort(model, args, true)),
+        ("tl", "") => print::tasks(&model.current_tasks, &model.operations.failed),
+        ("undo-ops", "") => add_change_mode(mode_before, mode, journal::undo(model)),
         ("w", "") => add_change_mode(
             mode_before,
             mode,
@@ -110,12 +188,62 @@ pub fn execute(cmd: &str, model: &mut Model) -> Vec<Action> {
             if !args.is_empty() {
                 let err = format!("command '{} {}' is not valid", cmd, args);
                 actions.push(Action::EmitMessages(vec![Message::Error(err)]));
+            } else if !cmd.is_empty() {
+                let err = format!("command '{}' is not valid", cmd);
+                actions.push(Action::EmitMessages(vec![Message::Error(err)]));
             }
             add_change_mode(mode_before, mode, actions)
         }
     }
 }
 
+fn execute_on_visual_selection(cmd: &str, model: &mut Model) -> Vec<Action> {
+    let cmd_with_args = match cmd.split_once(' ') {
+        Some(it) => it,
+        None => (cmd, ""),
+    };
+
+    tracing::debug!("executing command on visual selection: {:?}", cmd_with_args);
+
+    let mode_before = model.mode.clone();
+    let mode = get_mode_after_command(&model.mode_before);
+    let paths = std::mem::take(&mut model.files.visual_selection);
+
+    let actions = match cmd_with_args {
+        ("bulkrename", "") => return open_bulkrename(model, mode_before, mode, paths),
+        ("cp", args) => file::copy_paths(model, &paths, args, false),
+        ("cp!", args) => file::copy_paths(model, &paths, args, true),
+        ("d!", "") => file::delete_paths(&paths),
+        ("mv", args) => file::rename_paths(model, &paths, args, false),
+        ("mv!", args) => file::rename_paths(model, &paths, args, true),
+        (cmd, _) => {
+            let err = format!("command '{}' does not support ranges", cmd);
+            vec![Action::EmitMessages(vec![Message::Error(err)])]
+        }
+    };
+
+    add_change_mode(mode_before, mode, actions)
+}
+
+fn open_bulkrename(
+    model: &mut Model,
+    mode_before: Mode,
+    mode: Mode,
+    paths: Vec<PathBuf>,
+) -> Vec<Action> {
+    let is_open = model.files.bulk_rename.is_some();
+    let actions = bulkrename::open(model, paths);
+
+    // NOTE: normal enables editing the paths, thus only change into it if the buffer got replaced
+    let mode = if !is_open && model.files.bulk_rename.is_some() {
+        Mode::Normal
+    } else {
+        mode
+    };
+
+    add_change_mode(mode_before, mode, actions)
+}
+
 fn add_change_mode(mode_before: Mode, mode: Mode, mut actions: Vec<Action>) -> Vec<Action> {
     let emit = actions.iter_mut().find_map(|action| {
         if let Action::EmitMessages(messages) = action {
@@ -143,7 +271,7 @@ fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
         match mode {
             Mode::Command(_) => Mode::default(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::Visual => Mode::Navigation,
         }
     } else {
         Mode::default()
@@ -167,8 +295,74 @@ mod test {
         let result = super::get_mode_after_command(&mode_before);
         assert_eq!(result, Mode::Navigation);
 
+        let mode_before = Some(Mode::Visual);
+        let result = super::get_mode_after_command(&mode_before);
+        assert_eq!(result, Mode::Navigation);
+
         let mode_before = None;
         let result = super::get_mode_after_command(&mode_before);
         assert_eq!(result, Mode::Navigation);
     }
+
+    #[test]
+    fn bulkrename_without_paths_keeps_mode() {
+        use yeet_buffer::{
+            message::BufferMessage,
+            model::{CommandMode, Mode},
+        };
+        use yeet_keymap::message::KeymapMessage;
+
+        use crate::{action::Action, event::Message, model::Model};
+
+        let mut model = Model {
+            mode: Mode::Command(CommandMode::Command),
+            mode_before: Some(Mode::Navigation),
+            ..Default::default()
+        };
+
+        let actions = super::execute("bulkrename", &mut model);
+
+        assert!(model.files.bulk_rename.is_none());
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::EmitMessages(messages)] if matches!(
+                messages.as_slice(),
+                [Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(_, Mode::Navigation))), Message::Error(_)]
+            )
+        ));
+    }
+
+    #[test]
+    fn execute_handles_all_commands() {
+        use ratatui::layout::Rect;
+
+        use crate::{action::Action, event::Message, layout::AppLayout, model::Model};
+
+        let is_handled = |cmd: &str| {
+            let mut model = Model {
+                layout: AppLayout::new(Rect::new(0, 0, 80, 24), 1),
+                ..Default::default()
+            };
+            model.commandline.layout.buffer = Rect::new(0, 0, 80, 1);
+
+            let actions = super::execute(cmd, &mut model);
+            !actions.iter().any(|action| match action {
+                Action::EmitMessages(messages) => messages.iter().any(|message| {
+                    matches!(message, Message::Error(error) if error == &format!("command '{}' is not valid", cmd))
+                }),
+                _ => false,
+            })
+        };
+
+        // NOTE: some commands require arguments, thus are checked with and without
+        for cmd in super::COMMANDS {
+            assert!(
+                is_handled(cmd) || is_handled(&format!("{} 1", cmd)),
+                "command '{}' is not handled",
+                cmd
+            );
+        }
+
+        assert!(!is_handled("unknown"));
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use yeet_keymap::message::{KeymapMessage, PrintContent};
 
@@ -35,53 +35,94 @@ pub fn marks(marks: &Marks) -> Vec<Action> {
     vec![action::emit_keymap(KeymapMessage::Print(content))]
 }
 
-pub fn tasks(tasks: &HashMap<String, CurrentTask>) -> Vec<Action> {
+pub fn tasks(
+    tasks: &HashMap<String, CurrentTask>,
+    failed: &HashMap<PathBuf, String>,
+) -> Vec<Action> {
     let mut contents = vec![":tl".to_string(), "Id   Task".to_string()];
     let mut tasks: Vec<_> = tasks
         .values()
-        .map(|task| format!("{:<4} {}", task.id, task.external_id))
+        .map(|task| match task.progress {
+            Some((done, total)) => {
+                format!("{:<4} {} [{}/{}]", task.id, task.external_id, done, total)
+            }
+            None => format!("{:<4} {}", task.id, task.external_id),
+        })
         .collect();
 
     tasks.sort();
     contents.extend(tasks);
 
-    let content = contents
+    let mut content: Vec<_> = contents
         .iter()
         .map(|cntnt| PrintContent::Default(cntnt.to_string()))
         .collect();
 
+    let mut failed: Vec<_> = failed
+        .iter()
+        .map(|(path, reason)| format!("{}: {}", path.display(), reason))
+        .collect();
+
+    if !failed.is_empty() {
+        failed.sort();
+
+        content.push(PrintContent::Default("Failed operations".to_string()));
+        content.extend(failed.into_iter().map(PrintContent::Error));
+    }
+
     vec![action::emit_keymap(KeymapMessage::Print(content))]
 }
 
 pub fn qfix(qfix: &QuickFix) -> Vec<Action> {
     let max_width = (qfix.entries.len() + 1).to_string().len();
 
+    // NOTE: additional matched lines of an entry are listed below it without index
     let entries: Vec<_> = qfix
         .entries
         .iter()
         .enumerate()
-        .map(|(i, path)| (i + 1, path.to_string_lossy().to_string()))
-        .map(|(i, path)| format!("{:>max_width$} {}", i, path))
+        .flat_map(|(i, path)| match qfix.matches.get(path) {
+            Some(matches) if !matches.is_empty() => matches
+                .iter()
+                .enumerate()
+                .map(|(line, it)| {
+                    let index = if line == 0 {
+                        (i + 1).to_string()
+                    } else {
+                        String::new()
+                    };
+
+                    let content = format!(
+                        "{:>max_width$} {}:{}: {}",
+                        index,
+                        path.to_string_lossy(),
+                        it.line_number,
+                        it.text
+                    );
+
+                    (i, line, content)
+                })
+                .collect(),
+            _ => vec![(
+                i,
+                0,
+                format!("{:>max_width$} {}", i + 1, path.to_string_lossy()),
+            )],
+        })
         .collect();
 
-    let mut contents = vec![":cl".to_string()];
+    let mut content = vec![PrintContent::Default(":cl".to_string())];
     if entries.is_empty() {
-        contents.push("no entries".to_string());
+        content.push(PrintContent::Default("no entries".to_string()));
     } else {
-        contents.extend(entries);
-    }
-
-    let content = contents
-        .iter()
-        .enumerate()
-        .map(|(i, cntnt)| {
-            if i == qfix.current_index + 1 {
-                PrintContent::Information(cntnt.to_string())
+        content.extend(entries.into_iter().map(|(i, line, cntnt)| {
+            if i == qfix.current_index && line == qfix.current_match {
+                PrintContent::Information(cntnt)
             } else {
-                PrintContent::Default(cntnt.to_string())
+                PrintContent::Default(cntnt)
             }
-        })
-        .collect();
+        }));
+    }
 
     vec![action::emit_keymap(KeymapMessage::Print(content))]
 }
@@ -4,16 +4,20 @@ use yeet_keymap::message::{KeymapMessage, PrintContent};
 
 use crate::{
     action::{self, Action},
+    event::Message,
     model::{
         qfix::{CdoState, QFIX_SIGN_ID},
         Model,
     },
-    update::sign,
+    task::Task,
+    update::{search::get_search_regex, sign},
 };
 
 pub fn reset(model: &mut Model) -> Vec<Action> {
     model.qfix.entries.clear();
+    model.qfix.matches.clear();
     model.qfix.current_index = 0;
+    model.qfix.current_match = 0;
     sign::unset_sign_on_all_buffers(model, QFIX_SIGN_ID);
 
     Vec::new()
@@ -32,7 +36,7 @@ pub fn clear_in(model: &mut Model, path: &str) -> Vec<Action> {
 
         let path = current_path.join(bl.content.to_stripped_string());
         if model.qfix.entries.contains(&path) {
-            model.qfix.entries.retain(|p| p != &path);
+            model.qfix.remove(&path);
             sign::unset(bl, QFIX_SIGN_ID);
         }
     }
@@ -40,6 +44,26 @@ pub fn clear_in(model: &mut Model, path: &str) -> Vec<Action> {
     Vec::new()
 }
 
+pub fn grep(model: &Model, pattern: &str) -> Vec<Action> {
+    if pattern.is_empty() {
+        return vec![action::emit_error("usage: :grep <pattern>")];
+    }
+
+    let regex = match get_search_regex(pattern) {
+        Some(it) => it,
+        None => {
+            let err = format!("grep pattern '{}' is not a valid regex", pattern);
+            return vec![Action::EmitMessages(vec![Message::Error(err)])];
+        }
+    };
+
+    vec![Action::Task(Task::ExecuteGrep(
+        model.files.current.path.clone(),
+        regex,
+        model.settings.get_visibility(),
+    ))]
+}
+
 pub fn cdo(model: &mut Model, command: &str) -> Vec<Action> {
     tracing::debug!("cdo command set: {:?}", command);
 
@@ -52,6 +76,7 @@ pub fn cdo(model: &mut Model, command: &str) -> Vec<Action> {
 
 pub fn select_first(model: &mut Model) -> Vec<Action> {
     model.qfix.current_index = 0;
+    model.qfix.current_match = 0;
 
     match model.qfix.entries.first() {
         Some(it) => {
@@ -70,6 +95,22 @@ pub fn select_first(model: &mut Model) -> Vec<Action> {
 }
 
 pub fn next(model: &mut Model) -> Vec<Action> {
+    // NOTE: every match is a step of its own, but cdo executes its command once per path
+    let is_cdo = matches!(model.qfix.cdo, CdoState::Cdo(_, _));
+    let current = model.qfix.entries.get(model.qfix.current_index);
+    let match_count = current
+        .and_then(|path| model.qfix.matches.get(path))
+        .map_or(0, |matches| matches.len());
+
+    if let Some(path) = current.filter(|path| path.exists()) {
+        if !is_cdo && model.qfix.current_match + 1 < match_count {
+            model.qfix.current_match += 1;
+            return vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
+                path.clone(),
+            ))];
+        }
+    }
+
     let mut entry = model.qfix.entries.iter().enumerate().filter_map(|(i, p)| {
         if i > model.qfix.current_index && p.exists() {
             Some((i, p))
@@ -81,6 +122,7 @@ pub fn next(model: &mut Model) -> Vec<Action> {
     match entry.next() {
         Some((i, p)) => {
             model.qfix.current_index = i;
+            model.qfix.current_match = 0;
             vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
                 p.clone(),
             ))]
@@ -94,6 +136,15 @@ pub fn next(model: &mut Model) -> Vec<Action> {
 }
 
 pub fn previous(model: &mut Model) -> Vec<Action> {
+    if let Some(path) = model.qfix.entries.get(model.qfix.current_index) {
+        if path.exists() && model.qfix.current_match > 0 {
+            model.qfix.current_match -= 1;
+            return vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
+                path.clone(),
+            ))];
+        }
+    }
+
     let mut entry = model
         .qfix
         .entries
@@ -111,6 +162,12 @@ pub fn previous(model: &mut Model) -> Vec<Action> {
     match entry.next() {
         Some((i, p)) => {
             model.qfix.current_index = i;
+            model.qfix.current_match = model
+                .qfix
+                .matches
+                .get(p)
+                .map_or(0, |matches| matches.len().saturating_sub(1));
+
             vec![action::emit_keymap(KeymapMessage::NavigateToPathAsPreview(
                 p.clone(),
             ))]
@@ -132,7 +189,7 @@ pub fn invert_in_current(model: &mut Model) -> Vec<Action> {
 
         let path = current_path.join(bl.content.to_stripped_string());
         if model.qfix.entries.contains(&path) {
-            model.qfix.entries.retain(|p| p != &path);
+            model.qfix.remove(&path);
             sign::unset(bl, QFIX_SIGN_ID);
         } else {
             model.qfix.entries.push(path.clone());
@@ -142,3 +199,58 @@ pub fn invert_in_current(model: &mut Model) -> Vec<Action> {
 
     Vec::new()
 }
+
+mod test {
+    #[test]
+    fn grep_without_pattern_prints_usage() {
+        use crate::{action::Action, event::Message, model::Model};
+
+        let actions = super::grep(&Model::default(), "");
+
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::EmitMessages(messages)] if matches!(
+                messages.as_slice(),
+                [Message::Error(error)] if error == "usage: :grep <pattern>"
+            )
+        ));
+    }
+
+    #[test]
+    fn next_and_previous_step_through_matches() {
+        use std::fs;
+
+        use crate::model::{qfix::QuickFixMatch, Model};
+
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+        fs::write(&first, "").unwrap();
+        fs::write(&second, "").unwrap();
+
+        let mut model = Model::default();
+        model.qfix.entries = vec![first.clone(), second.clone()];
+        model.qfix.matches.insert(
+            first.clone(),
+            [1, 3]
+                .iter()
+                .map(|line_number| QuickFixMatch {
+                    line_number: *line_number,
+                    text: String::new(),
+                })
+                .collect(),
+        );
+
+        super::next(&mut model);
+        assert_eq!((0, 1), (model.qfix.current_index, model.qfix.current_match));
+
+        super::next(&mut model);
+        assert_eq!((1, 0), (model.qfix.current_index, model.qfix.current_match));
+
+        super::previous(&mut model);
+        assert_eq!((0, 1), (model.qfix.current_index, model.qfix.current_match));
+
+        super::previous(&mut model);
+        assert_eq!((0, 0), (model.qfix.current_index, model.qfix.current_match));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    action::{self, Action},
+    model::{FileWindow, Model},
+    settings::Column,
+    update::save::get_unsaved_changes_error,
+};
+
+use super::file;
+
+pub fn set(model: &mut Model, args: &str) -> Vec<Action> {
+    if let Some(value) = args.strip_prefix("columns=") {
+        return set_columns(model, value);
+    }
+
+    if args == "nocolumns" {
+        return set_columns(model, "");
+    }
+
+    // NOTE: confirming operations does not change listings, thus no refresh is required
+    let confirm = match args {
+        "confirm" => Some(true),
+        "noconfirm" => Some(false),
+        "confirm!" => Some(!model.settings.confirm_operations),
+        _ => None,
+    };
+
+    if let Some(confirm) = confirm {
+        model.settings.confirm_operations = confirm;
+        return Vec::new();
+    }
+
+    let settings = &mut model.settings;
+    let (option, value) = match args {
+        "hidden" => (&mut settings.show_hidden, Some(true)),
+        "nohidden" => (&mut settings.show_hidden, Some(false)),
+        "hidden!" => (&mut settings.show_hidden, None),
+        "ignore" => (&mut settings.respect_ignore, Some(true)),
+        "noignore" => (&mut settings.respect_ignore, Some(false)),
+        "ignore!" => (&mut settings.respect_ignore, None),
+        _ => {
+            return vec![action::emit_error(&format!(
+                "option '{}' is not valid",
+                args
+            ))]
+        }
+    };
+
+    let value = value.unwrap_or(!*option);
+    if *option == value {
+        return Vec::new();
+    }
+
+    if let Some(error) = get_refresh_error(&model.files) {
+        return vec![error];
+    }
+
+    *option = value;
+
+    file::refresh(model)
+}
+
+fn set_columns(model: &mut Model, value: &str) -> Vec<Action> {
+    let columns = match get_columns(value) {
+        Ok(it) => it,
+        Err(err) => return vec![action::emit_error(&err)],
+    };
+
+    if model.settings.columns == columns {
+        return Vec::new();
+    }
+
+    if let Some(error) = get_refresh_error(&model.files) {
+        return vec![error];
+    }
+
+    model.settings.columns = columns;
+
+    file::refresh(model)
+}
+
+fn get_columns(value: &str) -> Result<Vec<Column>, String> {
+    value
+        .split(',')
+        .map(|column| column.trim())
+        .filter(|column| !column.is_empty())
+        .map(|column| match column {
+            "mtime" => Ok(Column::Mtime),
+            "owner" => Ok(Column::Owner),
+            "permissions" => Ok(Column::Permissions),
+            "size" => Ok(Column::Size),
+            _ => Err(format!("column '{}' is not valid", column)),
+        })
+        .collect()
+}
+
+fn get_refresh_error(files: &FileWindow) -> Option<Action> {
+    if files.bulk_rename.is_some() {
+        return Some(action::emit_error(
+            "options are not available while bulk renaming",
+        ));
+    }
+
+    get_unsaved_changes_error(files)
+}
+
+mod test {
+    #[test]
+    fn get_columns() {
+        use crate::settings::Column;
+
+        let result = super::get_columns("size, permissions,mtime");
+        assert_eq!(
+            result,
+            Ok(vec![Column::Size, Column::Permissions, Column::Mtime])
+        );
+
+        assert_eq!(super::get_columns(""), Ok(Vec::new()));
+        assert!(super::get_columns("size,color").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use yeet_buffer::{
-    message::{BufferMessage, CursorDirection, Search, TextModification},
+    message::{BufferMessage, CursorDirection, LineDirection, Search, TextModification},
     model::{ansi::Ansi, BufferLine, CommandMode, Mode, SearchDirection},
     update::update_buffer,
 };
@@ -8,19 +8,28 @@ use yeet_keymap::message::{KeymapMessage, PrintContent};
 use crate::{
     action::{self, Action},
     event::Message,
-    model::Model,
+    model::{
+        CommandLine, CommandLineCompletion, CommandLinePrompt, Model, COMMANDLINE_COMPLETION_HEIGHT,
+    },
     update::{
+        command::completion::get_completion,
+        finder,
+        junkyard::resolve_paste_conflict,
+        mode::change_mode,
         register::get_register,
-        search::{clear_search, search_in_buffers},
+        save::resolve_path_changes_confirmation,
+        search::{clear_search, search_in_buffers, search_incrementally},
     },
 };
 
 use super::set_viewport_dimensions;
 
+const HISTORY_SIZE: usize = 100;
+
 pub fn update_commandline(model: &mut Model, message: Option<&BufferMessage>) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
     let commandline = &mut model.commandline;
@@ -32,7 +41,7 @@ pub fn update_commandline(model: &mut Model, message: Option<&BufferMessage>) ->
 
     if let Some(message) = message {
         match command_mode {
-            CommandMode::Command | CommandMode::Search(_) => {
+            CommandMode::Command | CommandMode::Find | CommandMode::Search(_) => {
                 update_buffer(viewport, cursor, &model.mode, buffer, message);
             }
             CommandMode::PrintMultiline => {}
@@ -49,10 +58,14 @@ pub fn update_commandline_on_modification(
 ) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
     let commandline = &mut model.commandline;
+    let is_completion_cleared = clear_completion(commandline);
+    let is_line_empty =
+        get_commandline_line(commandline).is_some_and(|line| line.content.is_empty());
+
     let buffer = &mut commandline.buffer;
     let cursor = &mut commandline.cursor;
     let viewport = &mut commandline.viewport;
@@ -60,18 +73,22 @@ pub fn update_commandline_on_modification(
     set_viewport_dimensions(viewport, &commandline.layout.buffer);
 
     match command_mode {
-        CommandMode::Command | CommandMode::Search(_) => {
+        CommandMode::Command | CommandMode::Find | CommandMode::Search(_) => {
+            commandline.history.index = None;
+
             let mut actions = Vec::new();
+            if is_completion_cleared {
+                actions.push(Action::EmitMessages(vec![Message::Rerender]));
+            }
+
             if let &TextModification::DeleteMotion(_, CursorDirection::Left) = modification {
-                if let Some(line) = buffer.lines.last() {
-                    if line.content.is_empty() {
-                        actions.push(action::emit_keymap(KeymapMessage::Buffer(
-                            BufferMessage::ChangeMode(
-                                model.mode.clone(),
-                                get_mode_after_command(&model.mode_before),
-                            ),
-                        )));
-                    }
+                if is_line_empty {
+                    actions.push(action::emit_keymap(KeymapMessage::Buffer(
+                        BufferMessage::ChangeMode(
+                            model.mode.clone(),
+                            get_mode_after_command(&model.mode_before),
+                        ),
+                    )));
                 }
             };
 
@@ -83,15 +100,15 @@ pub fn update_commandline_on_modification(
                 &BufferMessage::Modification(*repeat, modification.clone()),
             );
 
-            if matches!(model.mode, Mode::Command(CommandMode::Search(_))) {
-                let term = model
-                    .commandline
-                    .buffer
-                    .lines
-                    .last()
-                    .map(|bl| bl.content.to_stripped_string());
+            if let Mode::Command(CommandMode::Search(direction)) = &model.mode {
+                let direction = direction.clone();
+                let term = get_commandline_content(&model.commandline);
+
+                actions.extend(search_incrementally(model, &direction, term));
+            }
 
-                search_in_buffers(model, term);
+            if model.mode == Mode::Command(CommandMode::Find) {
+                actions.extend(finder::update_on_query(model));
             }
 
             actions
@@ -99,7 +116,18 @@ pub fn update_commandline_on_modification(
         CommandMode::PrintMultiline => {
             let mut messages = Vec::new();
             if let TextModification::Insert(cnt) = modification {
-                let action = if matches!(cnt.as_str(), ":" | "/" | "?") {
+                let prompt = model.commandline.prompt.take();
+                let is_prompt = prompt.is_some();
+                if let Some(prompt) = prompt {
+                    messages.extend(answer_prompt(model, prompt, cnt));
+                }
+
+                let commandline = &mut model.commandline;
+                let buffer = &mut commandline.buffer;
+                let cursor = &mut commandline.cursor;
+                let viewport = &mut commandline.viewport;
+
+                let action = if !is_prompt && matches!(cnt.as_str(), ":" | "/" | "?") {
                     model.mode = Mode::Command(match cnt.as_str() {
                         ":" => CommandMode::Command,
                         "/" => CommandMode::Search(SearchDirection::Down),
@@ -142,23 +170,41 @@ pub fn update_commandline_on_modification(
 pub fn update_commandline_on_execute(model: &mut Model) -> Vec<Action> {
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => return Vec::new(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
     };
 
+    model.commandline.completion = None;
+
     let messages = match command_mode {
         CommandMode::Command => {
-            if let Some(cmd) = model.commandline.buffer.lines.last() {
-                // TODO: add command history and show previous command not current (this enables g: as well)
-                model.register.command = Some(cmd.content.to_stripped_string());
+            if let Some(cmd) = get_commandline_content(&model.commandline) {
+                add_to_history(&mut model.commandline.history.commands, &cmd);
+                model.register.command = Some(cmd.clone());
 
-                vec![Message::Keymap(KeymapMessage::ExecuteCommandString(
-                    cmd.content.to_stripped_string(),
-                ))]
+                vec![Message::Keymap(KeymapMessage::ExecuteCommandString(cmd))]
             } else {
                 Vec::new()
             }
         }
+        CommandMode::Find => {
+            let mut messages = vec![Message::Keymap(KeymapMessage::Buffer(
+                BufferMessage::ChangeMode(
+                    model.mode.clone(),
+                    get_mode_after_command(&model.mode_before),
+                ),
+            ))];
+
+            if let Some(path) = finder::take_selected_path(model) {
+                messages.push(Message::Keymap(KeymapMessage::NavigateToPathAsPreview(
+                    path,
+                )));
+            }
+
+            messages
+        }
         CommandMode::PrintMultiline => {
+            model.commandline.prompt = None;
+
             vec![Message::Keymap(KeymapMessage::Buffer(
                 BufferMessage::ChangeMode(
                     model.mode.clone(),
@@ -167,12 +213,12 @@ pub fn update_commandline_on_execute(model: &mut Model) -> Vec<Action> {
             ))]
         }
         CommandMode::Search(direction) => {
-            model.register.searched = model
-                .commandline
-                .buffer
-                .lines
-                .last()
-                .map(|bl| (direction.clone(), bl.content.to_stripped_string()));
+            let term = get_commandline_content(&model.commandline);
+            if let Some(term) = &term {
+                add_to_history(&mut model.commandline.history.searches, term);
+            }
+
+            model.register.searched = term.map(|term| (direction.clone(), term));
 
             if model.register.searched.is_none() {
                 clear_search(model);
@@ -203,6 +249,18 @@ pub fn update_commandline_on_execute(model: &mut Model) -> Vec<Action> {
 }
 
 pub fn leave_commandline(model: &mut Model) -> Vec<Action> {
+    // NOTE: leaving a confirmation equals declining it, thus the changes stay editable in normal
+    let mut actions = match model.commandline.prompt.take() {
+        Some(CommandLinePrompt::ConfirmPathChanges(quit)) => {
+            resolve_path_changes_confirmation(model, quit, "")
+        }
+        Some(CommandLinePrompt::PasteConflict(_, _)) | None => Vec::new(),
+    };
+
+    model.commandline.completion = None;
+    model.commandline.history.index = None;
+    model.commandline.history.window = false;
+
     if matches!(model.mode, Mode::Command(CommandMode::Search(_))) {
         let content = get_register(&model.register, &'/');
         search_in_buffers(model, content);
@@ -216,16 +274,29 @@ pub fn leave_commandline(model: &mut Model) -> Vec<Action> {
         &BufferMessage::SetContent(vec![]),
     );
 
-    vec![action::emit_keymap(KeymapMessage::Buffer(
+    actions.push(action::emit_keymap(KeymapMessage::Buffer(
         BufferMessage::ChangeMode(
             model.mode.clone(),
             get_mode_after_command(&model.mode_before),
         ),
-    ))]
+    )));
+
+    actions
 }
 
 // TODO: buffer messages till command mode left
 pub fn print_in_commandline(model: &mut Model, content: &[PrintContent]) -> Vec<Action> {
+    // NOTE: entering command mode resets the commandline, thus printing happens afterwards
+    if content.len() > 1 && !model.mode.is_command() {
+        return vec![Action::EmitMessages(vec![
+            Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+                model.mode.clone(),
+                Mode::Command(CommandMode::PrintMultiline),
+            ))),
+            Message::Keymap(KeymapMessage::Print(content.to_vec())),
+        ])];
+    }
+
     let commandline = &mut model.commandline;
     let viewport = &mut commandline.viewport;
 
@@ -250,11 +321,13 @@ pub fn print_in_commandline(model: &mut Model, content: &[PrintContent]) -> Vec<
         .collect();
 
     let actions = if commandline.buffer.lines.len() > 1 {
-        let content = "Press ENTER or type command to continue";
-        commandline.buffer.lines.push(BufferLine {
-            content: Ansi::new(&format!("\x1b[94m{}\x1b[39m", content)),
-            ..Default::default()
-        });
+        if commandline.prompt.is_none() {
+            let content = "Press ENTER or type command to continue";
+            commandline.buffer.lines.push(BufferLine {
+                content: Ansi::new(&format!("\x1b[94m{}\x1b[39m", content)),
+                ..Default::default()
+            });
+        }
 
         if model.mode.is_command() {
             model.mode = Mode::Command(CommandMode::PrintMultiline);
@@ -288,14 +361,424 @@ pub fn print_in_commandline(model: &mut Model, content: &[PrintContent]) -> Vec<
     actions
 }
 
+pub fn navigate_commandline_history(model: &mut Model, direction: &LineDirection) -> Vec<Action> {
+    let command_mode = match &model.mode {
+        Mode::Command(CommandMode::Find | CommandMode::PrintMultiline) => return Vec::new(),
+        Mode::Command(it) => it.clone(),
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => return Vec::new(),
+    };
+
+    let commandline = &mut model.commandline;
+    set_viewport_dimensions(&mut commandline.viewport, &commandline.layout.buffer);
+
+    if commandline.history.window {
+        let direction = match direction {
+            LineDirection::Up => CursorDirection::Up,
+            LineDirection::Down => CursorDirection::Down,
+        };
+
+        update_buffer(
+            &mut commandline.viewport,
+            &mut commandline.cursor,
+            &model.mode,
+            &mut commandline.buffer,
+            &BufferMessage::MoveCursor(1, direction),
+        );
+
+        return Vec::new();
+    }
+
+    clear_completion(commandline);
+
+    let history = &mut commandline.history;
+    let entries = match command_mode {
+        CommandMode::Search(_) => &history.searches,
+        CommandMode::Command | CommandMode::Find | CommandMode::PrintMultiline => &history.commands,
+    };
+
+    if history.index.is_none() {
+        history.prefix = commandline
+            .buffer
+            .lines
+            .last()
+            .map(|bl| bl.content.to_stripped_string())
+            .unwrap_or_default();
+    }
+
+    let index = get_history_index(entries, history.index, &history.prefix, direction);
+    let content = match index {
+        Some(index) => entries[index].clone(),
+        None => history.prefix.clone(),
+    };
+    history.index = index;
+
+    if let Some(line) = commandline.buffer.lines.last_mut() {
+        line.content = Ansi::new(&content);
+    }
+
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::MoveCursor(1, CursorDirection::LineEnd),
+    );
+
+    if let CommandMode::Search(direction) = command_mode {
+        return search_incrementally(model, &direction, Some(content));
+    }
+
+    Vec::new()
+}
+
+pub fn open_commandline_history(model: &mut Model, command_mode: &CommandMode) -> Vec<Action> {
+    let history = &model.commandline.history;
+    let (prefix, entries) = match command_mode {
+        CommandMode::Command => (":", history.commands.clone()),
+        CommandMode::Search(SearchDirection::Down) => ("/", history.searches.clone()),
+        CommandMode::Search(SearchDirection::Up) => ("?", history.searches.clone()),
+        CommandMode::Find | CommandMode::PrintMultiline => return Vec::new(),
+    };
+
+    if entries.is_empty() {
+        return vec![Action::EmitMessages(vec![Message::Error(
+            "commandline history is empty".to_owned(),
+        )])];
+    }
+
+    let from = model.mode.clone();
+    let actions = change_mode(model, &from, &Mode::Command(command_mode.clone()));
+
+    // NOTE: the last line is empty to enable entering a new command within the window
+    let lines = entries
+        .iter()
+        .map(|entry| entry.as_str())
+        .chain(std::iter::once(""))
+        .map(|entry| BufferLine {
+            prefix: Some(prefix.to_owned()),
+            content: Ansi::new(entry),
+            ..Default::default()
+        })
+        .collect();
+
+    let commandline = &mut model.commandline;
+    commandline.history.index = None;
+    commandline.history.window = true;
+
+    set_viewport_dimensions(&mut commandline.viewport, &commandline.layout.buffer);
+
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::SetContent(lines),
+    );
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::MoveCursor(1, CursorDirection::Bottom),
+    );
+
+    actions
+}
+
+pub fn complete_commandline(model: &mut Model, direction: &LineDirection) -> Vec<Action> {
+    if model.mode != Mode::Command(CommandMode::Command) || model.commandline.history.window {
+        return Vec::new();
+    }
+
+    let completion = match model.commandline.completion.take() {
+        Some(mut completion) => {
+            completion.index = get_completion_index(&completion, direction);
+            completion
+        }
+        None => {
+            let line = model
+                .commandline
+                .buffer
+                .lines
+                .last()
+                .map(|bl| bl.content.to_stripped_string())
+                .unwrap_or_default();
+
+            let (base, candidates) = get_completion(model, &line);
+            if candidates.is_empty() {
+                return Vec::new();
+            }
+
+            let index = match direction {
+                LineDirection::Up => candidates.len() - 1,
+                LineDirection::Down => 0,
+            };
+
+            CommandLineCompletion {
+                base,
+                candidates,
+                index,
+            }
+        }
+    };
+
+    let content = format!(
+        "{}{}",
+        completion.base, completion.candidates[completion.index]
+    );
+
+    // NOTE: a single candidate gets applied directly to enable completing the next path segment
+    let mut lines = Vec::new();
+    if completion.candidates.len() > 1 {
+        lines.extend(get_completion_lines(&completion));
+        model.commandline.completion = Some(completion);
+    }
+
+    lines.push(BufferLine {
+        prefix: Some(":".to_owned()),
+        content: Ansi::new(&content),
+        ..Default::default()
+    });
+
+    let commandline = &mut model.commandline;
+    set_viewport_dimensions(&mut commandline.viewport, &commandline.layout.buffer);
+
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::SetContent(lines),
+    );
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::MoveCursor(1, CursorDirection::Bottom),
+    );
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::MoveCursor(1, CursorDirection::LineEnd),
+    );
+
+    // NOTE: the layout grows with the popup on rerender, thus the viewport must start at the top
+    commandline.viewport.vertical_index = 0;
+
+    vec![Action::EmitMessages(vec![Message::Rerender])]
+}
+
+fn get_completion_index(completion: &CommandLineCompletion, direction: &LineDirection) -> usize {
+    let len = completion.candidates.len();
+    match direction {
+        LineDirection::Up => (completion.index + len - 1) % len,
+        LineDirection::Down => (completion.index + 1) % len,
+    }
+}
+
+fn get_completion_lines(completion: &CommandLineCompletion) -> Vec<BufferLine> {
+    let start = (completion.index + 1).saturating_sub(COMMANDLINE_COMPLETION_HEIGHT);
+    completion
+        .candidates
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(COMMANDLINE_COMPLETION_HEIGHT)
+        .map(|(index, candidate)| {
+            let content = if index == completion.index {
+                format!("\x1b[7m{}\x1b[0m", candidate)
+            } else {
+                candidate.to_owned()
+            };
+
+            BufferLine {
+                prefix: Some(" ".to_owned()),
+                content: Ansi::new(&content),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn clear_completion(commandline: &mut CommandLine) -> bool {
+    if commandline.completion.take().is_none() {
+        return false;
+    }
+
+    let buffer = &mut commandline.buffer;
+    let len = buffer.lines.len();
+    if len > 1 {
+        buffer.lines.drain(..len - 1);
+    }
+
+    if let Some(cursor) = &mut commandline.cursor {
+        cursor.vertical_index = 0;
+    }
+    commandline.viewport.vertical_index = 0;
+
+    true
+}
+
+fn add_to_history(entries: &mut Vec<String>, entry: &str) {
+    if entry.trim().is_empty() {
+        return;
+    }
+
+    entries.retain(|it| it != entry);
+    entries.push(entry.to_owned());
+
+    if entries.len() > HISTORY_SIZE {
+        entries.drain(..entries.len() - HISTORY_SIZE);
+    }
+}
+
+fn get_history_index(
+    entries: &[String],
+    index: Option<usize>,
+    prefix: &str,
+    direction: &LineDirection,
+) -> Option<usize> {
+    match direction {
+        LineDirection::Up => {
+            let end = index.unwrap_or(entries.len());
+            (0..end)
+                .rev()
+                .find(|it| entries[*it].starts_with(prefix))
+                .or(index)
+        }
+        LineDirection::Down => {
+            let start = index? + 1;
+            (start..entries.len()).find(|it| entries[*it].starts_with(prefix))
+        }
+    }
+}
+
+fn get_commandline_line(commandline: &CommandLine) -> Option<&BufferLine> {
+    let buffer = &commandline.buffer;
+    if commandline.history.window {
+        commandline
+            .cursor
+            .as_ref()
+            .and_then(|cursor| buffer.lines.get(cursor.vertical_index))
+    } else {
+        buffer.lines.last()
+    }
+}
+
+fn get_commandline_content(commandline: &CommandLine) -> Option<String> {
+    get_commandline_line(commandline).map(|bl| bl.content.to_stripped_string())
+}
+
+fn answer_prompt(model: &mut Model, prompt: CommandLinePrompt, answer: &str) -> Vec<Action> {
+    match prompt {
+        CommandLinePrompt::ConfirmPathChanges(quit) => {
+            resolve_path_changes_confirmation(model, quit, answer)
+        }
+        CommandLinePrompt::PasteConflict(entries, path) => {
+            resolve_paste_conflict(entries, path, answer)
+        }
+    }
+}
+
 fn get_mode_after_command(mode_before: &Option<Mode>) -> Mode {
     if let Some(mode) = mode_before {
         match mode {
             Mode::Command(_) => unreachable!(),
             Mode::Insert | Mode::Normal => Mode::Normal,
-            Mode::Navigation => Mode::Navigation,
+            Mode::Navigation | Mode::Visual => Mode::Navigation,
         }
     } else {
         Mode::default()
     }
 }
+
+mod test {
+    #[test]
+    fn get_completion_lines_scrolls_to_selection() {
+        use crate::model::CommandLineCompletion;
+
+        let completion = CommandLineCompletion {
+            base: "cp ".to_owned(),
+            candidates: (0..10).map(|i| i.to_string()).collect(),
+            index: 9,
+        };
+
+        let result = super::get_completion_lines(&completion);
+
+        assert_eq!(8, result.len());
+        assert_eq!("2", result[0].content.to_stripped_string());
+        assert_eq!("9", result[7].content.to_stripped_string());
+    }
+
+    #[test]
+    fn add_to_history_moves_duplicate_to_end() {
+        let mut entries = vec!["e".to_owned(), "w".to_owned()];
+
+        super::add_to_history(&mut entries, "e");
+        super::add_to_history(&mut entries, " ");
+
+        assert_eq!(vec!["w".to_owned(), "e".to_owned()], entries);
+    }
+
+    #[test]
+    fn get_history_index_with_prefix() {
+        use yeet_buffer::message::LineDirection;
+
+        let entries = vec![
+            "set hidden".to_owned(),
+            "e".to_owned(),
+            "set sort=mtime".to_owned(),
+        ];
+
+        let index = super::get_history_index(&entries, None, "set", &LineDirection::Up);
+        assert_eq!(Some(2), index);
+
+        let index = super::get_history_index(&entries, index, "set", &LineDirection::Up);
+        assert_eq!(Some(0), index);
+
+        let index = super::get_history_index(&entries, index, "set", &LineDirection::Up);
+        assert_eq!(Some(0), index);
+
+        let index = super::get_history_index(&entries, index, "set", &LineDirection::Down);
+        assert_eq!(Some(2), index);
+
+        let index = super::get_history_index(&entries, index, "set", &LineDirection::Down);
+        assert_eq!(None, index);
+    }
+
+    #[test]
+    fn leave_commandline_declines_path_changes_confirmation() {
+        use yeet_buffer::{
+            message::BufferMessage,
+            model::{CommandMode, Mode},
+        };
+        use yeet_keymap::message::KeymapMessage;
+
+        use crate::{
+            action::Action,
+            event::Message,
+            model::{CommandLinePrompt, Model},
+        };
+
+        let mut model = Model {
+            mode: Mode::Command(CommandMode::PrintMultiline),
+            mode_before: Some(Mode::Navigation),
+            ..Default::default()
+        };
+        model.commandline.prompt = Some(CommandLinePrompt::ConfirmPathChanges(None));
+
+        let actions = super::leave_commandline(&mut model);
+
+        assert!(model.commandline.prompt.is_none());
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::EmitMessages(messages)] if matches!(
+                messages.as_slice(),
+                [Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(_, Mode::Normal)))]
+            )
+        ));
+    }
+}
@@ -15,7 +15,7 @@ use super::{
     history::get_selection_from_history,
     register::{get_direction_from_search_register, get_register},
     search::search_in_buffers,
-    selection, update_current,
+    selection, update_focused,
 };
 
 pub fn set_cursor_index_to_selection(
@@ -75,9 +75,9 @@ pub fn move_cursor(model: &mut Model, rpt: &usize, mtn: &CursorDirection) -> Vec
         };
 
         let msg = BufferMessage::MoveCursor(*rpt, CursorDirection::Search(dr.clone()));
-        update_current(model, &msg);
+        update_focused(model, &msg);
     } else {
-        update_current(model, &msg);
+        update_focused(model, &msg);
     };
 
     let mut actions = Vec::new();
@@ -1,5 +1,9 @@
-use std::{mem, path::PathBuf};
+use std::{
+    mem,
+    path::{Path, PathBuf},
+};
 
+use lscolors::{Indicator, LsColors};
 use yeet_buffer::{
     message::{BufferMessage, ViewPortDirection},
     model::{ansi::Ansi, BufferLine, Cursor, CursorPosition, Mode},
@@ -10,11 +14,16 @@ use crate::{
     action::Action,
     event::ContentKind,
     model::{DirectoryBufferState, Model, WindowType},
+    task::column::add_columns,
     update::{
         cursor::{set_cursor_index_to_selection, set_cursor_index_with_history},
+        git::load_status,
         history::get_selection_from_history,
+        path::{get_pending_edits, get_released_names, get_target_names, mark_removed_path},
+        search::get_search_regex,
         selection,
-        sign::{set_sign_if_marked, set_sign_if_qfix},
+        sign::{set_sign_if_git, set_sign_if_marked, set_sign_if_operation, set_sign_if_qfix},
+        sort::get_sorted_lines,
     },
 };
 
@@ -22,10 +31,22 @@ use crate::{
 pub fn update_on_enumeration_change(
     model: &mut Model,
     path: &PathBuf,
-    contents: &[(ContentKind, String)],
+    contents: &[(ContentKind, String, Option<String>)],
     selection: &Option<String>,
 ) -> Vec<Action> {
-    // TODO: handle unsaved changes
+    // NOTE: while editing, enumerated paths are merged into the buffer to keep the pending edits
+    let released = if path == &model.files.current.path {
+        get_pending_edits(model).map(|changes| get_released_names(&changes))
+    } else {
+        None
+    };
+
+    let filter = model
+        .files
+        .filters
+        .get(path)
+        .and_then(|filter| get_search_regex(filter));
+
     let directories = model.files.get_mut_directories();
     if let Some((path, viewport, cursor, buffer)) =
         directories.into_iter().find(|(p, _, _, _)| p == path)
@@ -33,17 +54,41 @@ pub fn update_on_enumeration_change(
         tracing::trace!("enumeration changed for buffer: {:?}", path);
 
         let is_first_changed_event = buffer.lines.is_empty();
-        let content = contents
+        let content: Vec<_> = contents
             .iter()
-            .map(|(knd, cntnt)| {
-                let mut line = from_enumeration(cntnt, knd);
+            .filter(|(_, cntnt, _)| match &filter {
+                Some(regex) => regex.is_match(cntnt),
+                None => true,
+            })
+            .map(|(knd, cntnt, columns)| {
+                let mut line = from_enumeration(&model.settings.ls_colors, cntnt, knd);
+                if let Some(columns) = columns {
+                    add_columns(&mut line, columns);
+                }
                 set_sign_if_marked(&model.marks, &mut line, &path.join(cntnt));
                 set_sign_if_qfix(&model.qfix, &mut line, &path.join(cntnt));
+                set_sign_if_git(&model.git, &mut line, &path.join(cntnt));
+                set_sign_if_operation(&model.operations, &mut line, &path.join(cntnt));
 
                 line
             })
             .collect();
 
+        if let Some(released) = released {
+            let existing: Vec<_> = buffer
+                .lines
+                .iter()
+                .map(|line| line.content.to_stripped_string())
+                .collect();
+
+            buffer.lines.extend(content.into_iter().filter(|line| {
+                let name = line.content.to_stripped_string();
+                !existing.contains(&name) && !released.contains(&name)
+            }));
+
+            return Vec::new();
+        }
+
         update_buffer(
             viewport,
             cursor,
@@ -78,25 +123,28 @@ pub fn update_on_enumeration_change(
 pub fn update_on_enumeration_finished(
     model: &mut Model,
     path: &PathBuf,
-    contents: &[(ContentKind, String)],
+    contents: &[(ContentKind, String, Option<String>)],
     selection: &Option<String>,
 ) -> Vec<Action> {
     update_on_enumeration_change(model, path, contents, selection);
 
+    let mut actions = mark_missing_paths(model, path, contents);
+    actions.extend(load_status(model, path));
     if model.mode != Mode::Navigation {
-        return Vec::new();
+        return actions;
     }
 
     let directories = model.files.get_mut_directories();
     if let Some((_, viewport, cursor, buffer)) =
         directories.into_iter().find(|(p, _, _, _)| p == path)
     {
+        let lines = get_sorted_lines(&model.settings.sort, path, buffer.lines.drain(..).collect());
         update_buffer(
             viewport,
             cursor,
             &model.mode,
             buffer,
-            &BufferMessage::SortContent(super::SORT),
+            &BufferMessage::SetContent(lines),
         );
 
         if let Some(selection) = selection {
@@ -148,7 +196,6 @@ pub fn update_on_enumeration_finished(
         model.files.current.state,
     );
 
-    let mut actions = Vec::new();
     if model.files.current.state == DirectoryBufferState::Loading {
         return actions;
     }
@@ -168,15 +215,160 @@ pub fn update_on_enumeration_finished(
     actions
 }
 
-// TODO: move to ansi before
-pub fn from_enumeration(content: &String, kind: &ContentKind) -> BufferLine {
-    let content = match kind {
-        ContentKind::Directory => format!("\x1b[94m{}\x1b[39m", content),
-        _ => content.to_string(),
+// NOTE: while editing, the buffer is merged with the enumeration, thus paths removed in the
+// meantime must be marked like removals reported by the watcher
+fn mark_missing_paths(
+    model: &mut Model,
+    path: &Path,
+    contents: &[(ContentKind, String, Option<String>)],
+) -> Vec<Action> {
+    if path != model.files.current.path || model.files.bulk_rename.is_some() {
+        return Vec::new();
+    }
+
+    let changes = match get_pending_edits(model) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    let targets = get_target_names(&changes);
+    let mut missing: Vec<_> = model
+        .files
+        .current
+        .buffer
+        .lines
+        .iter()
+        .map(|line| line.content.to_stripped_string())
+        .chain(get_released_names(&changes))
+        .filter(|name| !name.is_empty() && !targets.contains(name))
+        .filter(|name| !contents.iter().any(|(_, content, _)| content == name))
+        .collect();
+
+    missing.sort();
+    missing.dedup();
+
+    missing
+        .into_iter()
+        .flat_map(|name| mark_removed_path(model, &path.join(name), &changes))
+        .collect()
+}
+
+pub fn from_enumeration(ls_colors: &LsColors, content: &str, kind: &ContentKind) -> BufferLine {
+    let indicator = match kind {
+        ContentKind::BlockDevice => Indicator::BlockDevice,
+        ContentKind::BrokenSymlink(_) => Indicator::OrphanedSymbolicLink,
+        ContentKind::CharacterDevice => Indicator::CharacterDevice,
+        ContentKind::Directory => Indicator::Directory,
+        ContentKind::Executable => Indicator::ExecutableFile,
+        ContentKind::Fifo => Indicator::FIFO,
+        ContentKind::File => Indicator::RegularFile,
+        ContentKind::Socket => Indicator::Socket,
+        ContentKind::Symlink(_) => Indicator::SymbolicLink,
+    };
+
+    // NOTE: like ls, only regular files are styled by their name (e.g. *.rs)
+    let style = match kind {
+        ContentKind::File => ls_colors
+            .style_for_str(content)
+            .or_else(|| ls_colors.style_for_indicator(indicator)),
+        _ => ls_colors.style_for_indicator(indicator),
+    };
+
+    let styled = match style {
+        Some(style) => style.to_nu_ansi_term_style().paint(content).to_string(),
+        None => content.to_owned(),
+    };
+
+    let suffix = match kind {
+        ContentKind::BrokenSymlink(target) | ContentKind::Symlink(target) => {
+            Some(format!("-> {}", target.to_string_lossy()))
+        }
+        _ => None,
     };
 
     BufferLine {
-        content: Ansi::new(&content),
+        content: Ansi::new(&styled),
+        suffix,
         ..Default::default()
     }
 }
+
+mod test {
+    #[test]
+    fn from_enumeration_styles() {
+        use lscolors::LsColors;
+
+        use crate::event::ContentKind;
+
+        let ls_colors = LsColors::from_string("di=01;34:ex=01;32:*.rs=00;33");
+
+        let line = super::from_enumeration(&ls_colors, "src", &ContentKind::Directory);
+        assert_eq!(line.content.to_string(), "\x1b[1;34msrc\x1b[0m");
+
+        let line = super::from_enumeration(&ls_colors, "main.rs", &ContentKind::File);
+        assert_eq!(line.content.to_string(), "\x1b[33mmain.rs\x1b[0m");
+
+        let line = super::from_enumeration(&ls_colors, "run.rs", &ContentKind::Executable);
+        assert_eq!(line.content.to_string(), "\x1b[1;32mrun.rs\x1b[0m");
+    }
+
+    #[test]
+    fn from_enumeration_symlink_target_in_suffix() {
+        use std::path::PathBuf;
+
+        use lscolors::LsColors;
+
+        use crate::event::ContentKind;
+
+        let kind = ContentKind::BrokenSymlink(PathBuf::from("../missing"));
+        let line = super::from_enumeration(&LsColors::empty(), "link", &kind);
+
+        assert_eq!(line.content.to_stripped_string(), "link");
+        assert_eq!(line.suffix, Some("-> ../missing".to_owned()));
+    }
+
+    #[test]
+    fn enumeration_finished_marks_missing_paths_with_pending_edits() {
+        use std::path::PathBuf;
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+
+        use crate::{
+            event::ContentKind,
+            model::{Model, ERROR_SIGN_ID, REMOVED_SIGN_ID},
+        };
+
+        let path = PathBuf::from("/root");
+        let mut model = Model {
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        model.files.current.path = path.clone();
+        model.files.current.buffer.lines = ["a", "b", "renamed", "added"]
+            .iter()
+            .map(|name| BufferLine {
+                content: Ansi::new(name),
+                ..Default::default()
+            })
+            .collect();
+
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![
+                BufferChanged::Content(2, Ansi::new("c"), Ansi::new("renamed")),
+                BufferChanged::LineAdded(3, Ansi::new("added")),
+            ],
+        );
+
+        let contents = vec![(ContentKind::File, "a".to_owned(), None)];
+        let actions = super::update_on_enumeration_finished(&mut model, &path, &contents, &None);
+
+        let lines = &model.files.current.buffer.lines;
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].signs.is_empty());
+        assert!(lines[1].signs.iter().any(|s| s.id == REMOVED_SIGN_ID));
+        assert!(lines[2].signs.iter().any(|s| s.id == ERROR_SIGN_ID));
+        assert!(lines[3].signs.is_empty());
+        assert!(!actions.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    action::{self, Action},
+    model::{Model, WindowType},
+};
+
+use super::{
+    save::get_unsaved_changes_error, search::get_search_regex,
+    selection::get_current_selected_bufferline,
+};
+
+pub fn set_filter(model: &mut Model, pattern: &str) -> Vec<Action> {
+    if model.files.bulk_rename.is_some() {
+        return vec![action::emit_error(
+            "filter is not available while bulk renaming",
+        )];
+    }
+
+    if let Some(error) = get_unsaved_changes_error(&model.files) {
+        return vec![error];
+    }
+
+    let path = model.files.current.path.clone();
+    if pattern.is_empty() {
+        if model.files.filters.remove(&path).is_none() {
+            return Vec::new();
+        }
+    } else if get_search_regex(pattern).is_some() {
+        model.files.filters.insert(path.clone(), pattern.to_owned());
+    } else {
+        return vec![action::emit_error(&format!(
+            "filter '{}' is not valid",
+            pattern
+        ))];
+    }
+
+    let selection = get_current_selected_bufferline(model)
+        .map(|line| line.content.to_stripped_string())
+        .filter(|content| !content.is_empty());
+
+    vec![Action::Load(WindowType::Current, path, selection)]
+}
+
+pub fn is_filtered_out(filters: &HashMap<PathBuf, String>, path: &Path) -> bool {
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, name.to_string_lossy()),
+        _ => return false,
+    };
+
+    match filters
+        .get(parent)
+        .and_then(|filter| get_search_regex(filter))
+    {
+        Some(regex) => !regex.is_match(&name),
+        None => false,
+    }
+}
+
+mod test {
+    #[test]
+    fn is_filtered_out() {
+        use std::{collections::HashMap, path::PathBuf};
+
+        let mut filters = HashMap::new();
+        filters.insert(PathBuf::from("/yeet"), "\\.rs$".to_owned());
+
+        assert!(!super::is_filtered_out(
+            &filters,
+            &PathBuf::from("/yeet/lib.rs")
+        ));
+        assert!(super::is_filtered_out(
+            &filters,
+            &PathBuf::from("/yeet/Cargo.toml")
+        ));
+        assert!(!super::is_filtered_out(
+            &filters,
+            &PathBuf::from("/other/Cargo.toml")
+        ));
+    }
+}
@@ -0,0 +1,382 @@
+use std::{
+    mem,
+    path::{PathBuf, MAIN_SEPARATOR},
+};
+
+use yeet_buffer::{
+    message::{BufferMessage, LineDirection},
+    model::{ansi::Ansi, BufferLine, CommandMode, Mode},
+    update::update_buffer,
+};
+
+use crate::{
+    action::Action,
+    event::Message,
+    model::{
+        finder::{Finder, FINDER_HEIGHT},
+        Model,
+    },
+    task::Task,
+};
+
+use super::{commandline::leave_commandline, mode::change_mode, qfix, selection};
+
+pub fn open(model: &mut Model) -> Vec<Action> {
+    let to = Mode::Command(CommandMode::Find);
+    let mut actions = if model.mode.is_command() {
+        model.mode = to;
+        vec![Action::ModeChanged]
+    } else {
+        let from = model.mode.clone();
+        change_mode(model, &from, &to)
+    };
+
+    let root = model.files.current.path.clone();
+    model.finder = Some(Finder {
+        root: root.clone(),
+        ..Default::default()
+    });
+
+    let commandline = &mut model.commandline;
+    update_buffer(
+        &mut commandline.viewport,
+        &mut commandline.cursor,
+        &model.mode,
+        &mut commandline.buffer,
+        &BufferMessage::SetContent(vec![BufferLine {
+            prefix: Some(">".to_owned()),
+            ..Default::default()
+        }]),
+    );
+
+    set_finder_lines(model);
+
+    actions.push(Action::Task(Task::FindPaths(
+        root,
+        model.settings.get_visibility(),
+    )));
+    actions.push(Action::EmitMessages(vec![Message::Rerender]));
+    actions
+}
+
+pub fn close(model: &mut Model) -> Vec<Action> {
+    let finder = match model.finder.take() {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    let identifier = Task::FindPaths(finder.root, model.settings.get_visibility()).to_string();
+    if let Some(task) = model.current_tasks.get(&identifier) {
+        task.token.cancel();
+    }
+
+    // NOTE: the preview shows the selected finder entry, thus the current selection gets restored
+    selection::load_preview_on_selection_change(model)
+}
+
+pub fn take_selected_path(model: &mut Model) -> Option<PathBuf> {
+    let path = model.finder.as_ref()?.get_selected_path()?;
+    close(model);
+
+    Some(path)
+}
+
+pub fn update_on_paths_added(model: &mut Model, root: PathBuf, paths: Vec<PathBuf>) -> Vec<Action> {
+    let finder = match &mut model.finder {
+        Some(it) if it.root == root => it,
+        _ => return Vec::new(),
+    };
+
+    // NOTE: only new paths get scored, existing matches keep their score
+    let start = finder.paths.len();
+    finder.paths.extend(paths);
+    add_matches(finder, start..finder.paths.len());
+
+    set_finder_lines(model);
+    load_preview(model)
+}
+
+pub fn update_on_query(model: &mut Model) -> Vec<Action> {
+    let query = model
+        .commandline
+        .buffer
+        .lines
+        .last()
+        .map(|bl| bl.content.to_stripped_string())
+        .unwrap_or_default();
+
+    let finder = match &mut model.finder {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    finder.index = 0;
+    let previous = mem::replace(&mut finder.query, query);
+    update_matches(finder, &previous);
+
+    set_finder_lines(model);
+    load_preview(model)
+}
+
+pub fn navigate(model: &mut Model, direction: &LineDirection) -> Vec<Action> {
+    let finder = match &mut model.finder {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    // NOTE: the best match is shown directly above the input, thus up selects worse matches
+    finder.index = match direction {
+        LineDirection::Up => (finder.index + 1).min(finder.matches.len().saturating_sub(1)),
+        LineDirection::Down => finder.index.saturating_sub(1),
+    };
+
+    set_finder_lines(model);
+    load_preview(model)
+}
+
+pub fn add_matches_to_qfix(model: &mut Model) -> Vec<Action> {
+    let paths = match &model.finder {
+        Some(finder) => finder
+            .matches
+            .iter()
+            .map(|(_, _, index)| finder.root.join(&finder.paths[*index]))
+            .collect(),
+        None => return Vec::new(),
+    };
+
+    let mut actions = qfix::add(model, paths);
+    actions.extend(leave_commandline(model));
+    actions
+}
+
+fn load_preview(model: &Model) -> Vec<Action> {
+    let path = match &model.finder {
+        Some(finder) => finder.get_selected_path(),
+        None => return Vec::new(),
+    };
+
+    if path.is_none() {
+        return Vec::new();
+    }
+
+    selection::load_preview_on_change(model, path)
+}
+
+fn update_matches(finder: &mut Finder, previous: &str) {
+    // NOTE: extending the query only drops matches, thus only current matches get scored again
+    let indices: Vec<_> = if !previous.is_empty() && finder.query.starts_with(previous) {
+        finder.matches.iter().map(|(_, _, index)| *index).collect()
+    } else {
+        (0..finder.paths.len()).collect()
+    };
+
+    finder.matches.clear();
+    add_matches(finder, indices.into_iter());
+}
+
+fn add_matches(finder: &mut Finder, indices: impl Iterator<Item = usize>) {
+    let query: Vec<_> = finder.query.chars().collect();
+    let ignore_case = !query.iter().any(|c| c.is_uppercase());
+
+    let paths = &finder.paths;
+    finder.matches.extend(indices.filter_map(|index| {
+        let path = paths[index].to_string_lossy();
+        let score = get_score(&query, &path, ignore_case)?;
+
+        Some((score, path.chars().count(), index))
+    }));
+
+    if !query.is_empty() {
+        finder
+            .matches
+            .sort_by(|(s1, l1, i1), (s2, l2, i2)| s2.cmp(s1).then(l1.cmp(l2)).then(i1.cmp(i2)));
+    }
+
+    finder.index = finder.index.min(finder.matches.len().saturating_sub(1));
+}
+
+fn get_score(query: &[char], candidate: &str, ignore_case: bool) -> Option<usize> {
+    let chars: Vec<_> = candidate.chars().collect();
+    let name_start = chars
+        .iter()
+        .rposition(|c| c == &MAIN_SEPARATOR)
+        .map_or(0, |index| index + 1);
+
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for query_char in query {
+        let offset = chars[position..]
+            .iter()
+            .position(|c| is_char_match(*c, *query_char, ignore_case))?;
+
+        let index = position + offset;
+
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || matches!(chars[index - 1], '/' | '\\' | '_' | '-' | '.' | ' ') {
+            score += 3;
+        }
+        if index >= name_start {
+            score += 2;
+        }
+
+        previous = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+fn is_char_match(candidate: char, query: char, ignore_case: bool) -> bool {
+    if ignore_case {
+        candidate.to_lowercase().eq(query.to_lowercase())
+    } else {
+        candidate == query
+    }
+}
+
+fn set_finder_lines(model: &mut Model) {
+    let finder = match &model.finder {
+        Some(it) => it,
+        None => return,
+    };
+
+    let start = (finder.index + 1).saturating_sub(FINDER_HEIGHT);
+    let mut lines: Vec<_> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(FINDER_HEIGHT)
+        .map(|(index, (_, _, path_index))| {
+            let path = finder.paths[*path_index].to_string_lossy();
+            let content = if index == finder.index {
+                format!("\x1b[7m{}\x1b[0m", path)
+            } else {
+                path.to_string()
+            };
+
+            BufferLine {
+                prefix: Some(" ".to_owned()),
+                content: Ansi::new(&content),
+                ..Default::default()
+            }
+        })
+        .rev()
+        .collect();
+
+    // NOTE: padding keeps the height of the finder stable while typing
+    let padding = FINDER_HEIGHT - lines.len();
+    lines.splice(
+        0..0,
+        (0..padding).map(|_| BufferLine {
+            prefix: Some(" ".to_owned()),
+            ..Default::default()
+        }),
+    );
+
+    let commandline = &mut model.commandline;
+    let input = commandline
+        .buffer
+        .lines
+        .pop()
+        .unwrap_or_else(|| BufferLine {
+            prefix: Some(">".to_owned()),
+            ..Default::default()
+        });
+
+    lines.push(input);
+    commandline.buffer.lines = lines;
+
+    if let Some(cursor) = &mut commandline.cursor {
+        cursor.vertical_index = FINDER_HEIGHT;
+    }
+    commandline.viewport.vertical_index = 0;
+}
+
+mod test {
+    #[test]
+    fn get_score_prefers_consecutive_matches() {
+        let query: Vec<_> = "main".chars().collect();
+
+        let consecutive = super::get_score(&query, "src/main.rs", true);
+        let scattered = super::get_score(&query, "src/model/action.rs", true);
+        let missing = super::get_score(&query, "src/lib.rs", true);
+
+        assert!(consecutive > scattered);
+        assert_eq!(None, missing);
+    }
+
+    #[test]
+    fn get_score_with_smart_case() {
+        let query: Vec<_> = "Lib".chars().collect();
+
+        assert_eq!(None, super::get_score(&query, "src/lib.rs", false));
+        assert!(super::get_score(&query, "src/Lib.rs", false).is_some());
+    }
+
+    #[test]
+    fn update_matches_prefers_file_names() {
+        use std::path::PathBuf;
+
+        use crate::model::finder::Finder;
+
+        let mut finder = Finder {
+            paths: vec![
+                PathBuf::from("yeet-frontend/src/update/mod.rs"),
+                PathBuf::from("yeet-buffer/src/model/mod.rs"),
+                PathBuf::from("README.md"),
+            ],
+            query: "mod".to_owned(),
+            ..Default::default()
+        };
+
+        super::update_matches(&mut finder, "");
+
+        let indices: Vec<_> = finder.matches.iter().map(|(_, _, index)| *index).collect();
+        assert_eq!(vec![0, 1], indices);
+    }
+
+    #[test]
+    fn update_on_paths_added_scores_new_paths() {
+        use std::path::PathBuf;
+
+        use crate::model::{finder::Finder, Model};
+
+        let root = PathBuf::from("/root");
+        let mut model = Model {
+            finder: Some(Finder {
+                root: root.clone(),
+                query: "mod".to_owned(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        super::update_on_paths_added(&mut model, root.clone(), vec![PathBuf::from("a/mod.rs")]);
+        super::update_on_paths_added(
+            &mut model,
+            root,
+            vec![PathBuf::from("README.md"), PathBuf::from("mod.rs")],
+        );
+
+        let finder = model.finder.as_mut().unwrap();
+        let indices: Vec<_> = finder.matches.iter().map(|(_, _, index)| *index).collect();
+        assert_eq!(vec![2, 0], indices);
+
+        finder.query = "mod.r".to_owned();
+        super::update_matches(finder, "mod");
+
+        let indices: Vec<_> = finder.matches.iter().map(|(_, _, index)| *index).collect();
+        assert_eq!(vec![2, 0], indices);
+
+        finder.query = "READ".to_owned();
+        super::update_matches(finder, "mod.r");
+
+        let indices: Vec<_> = finder.matches.iter().map(|(_, _, index)| *index).collect();
+        assert_eq!(vec![1], indices);
+    }
+}
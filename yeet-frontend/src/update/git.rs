@@ -0,0 +1,163 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    action::Action,
+    model::{
+        git::{GitStatus, GIT_SIGN_ID},
+        Model,
+    },
+    task::Task,
+};
+
+use super::sign::{set_sign_if_git, unset};
+
+pub fn load_status(model: &Model, path: &Path) -> Vec<Action> {
+    // NOTE: known repositories are only reloaded for the current directory to keep git calls low
+    let target = match model.git.get_repository(path) {
+        Some(root) if path == model.files.current.path => root,
+        Some(_) => return Vec::new(),
+        None if model.git.unavailable.contains(path) => return Vec::new(),
+        None => path,
+    };
+
+    vec![Action::Task(Task::LoadGitStatus(target.to_path_buf()))]
+}
+
+pub fn refresh_status(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
+    // NOTE: changed directories without repository are checked again to catch git init and clone
+    let mut targets: Vec<_> = paths
+        .iter()
+        .filter_map(|path| path.parent())
+        .filter(|parent| model.git.unavailable.remove(*parent))
+        .map(|parent| parent.to_path_buf())
+        .collect();
+
+    targets.extend(
+        paths
+            .iter()
+            .filter_map(|path| model.git.get_repository(path))
+            .map(|root| root.to_path_buf()),
+    );
+
+    targets.sort();
+    targets.dedup();
+
+    targets
+        .into_iter()
+        .map(|target| Action::Task(Task::LoadGitStatus(target)))
+        .collect()
+}
+
+pub fn update_on_status(
+    model: &mut Model,
+    root: PathBuf,
+    entries: Vec<(PathBuf, GitStatus)>,
+) -> Vec<Action> {
+    let entries = get_entries_with_parents(&root, entries);
+    model.git.repositories.insert(root, entries);
+
+    for (path, _, _, buffer) in model.files.get_mut_directories() {
+        for line in buffer.lines.iter_mut() {
+            unset(line, GIT_SIGN_ID);
+
+            let name = line.content.to_stripped_string();
+            set_sign_if_git(&model.git, line, &path.join(name));
+        }
+    }
+
+    Vec::new()
+}
+
+pub fn update_on_unavailable(model: &mut Model, path: PathBuf) -> Vec<Action> {
+    model.git.unavailable.insert(path);
+    Vec::new()
+}
+
+fn get_entries_with_parents(
+    root: &Path,
+    entries: Vec<(PathBuf, GitStatus)>,
+) -> HashMap<PathBuf, GitStatus> {
+    let mut result: HashMap<PathBuf, GitStatus> = HashMap::new();
+    for (path, status) in entries {
+        // NOTE: directories show the most important status of their content
+        if status != GitStatus::Ignored {
+            let parents = path
+                .ancestors()
+                .skip(1)
+                .take_while(|parent| parent.starts_with(root) && *parent != root);
+
+            for parent in parents {
+                insert_with_priority(&mut result, parent.to_path_buf(), status.clone());
+            }
+        }
+
+        insert_with_priority(&mut result, path, status);
+    }
+
+    result
+}
+
+fn insert_with_priority(
+    entries: &mut HashMap<PathBuf, GitStatus>,
+    path: PathBuf,
+    status: GitStatus,
+) {
+    match entries.get(&path) {
+        Some(current) if current.get_priority() >= status.get_priority() => {}
+        _ => {
+            entries.insert(path, status);
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn get_entries_with_parents() {
+        use std::path::{Path, PathBuf};
+
+        use crate::model::git::GitStatus;
+
+        let entries = vec![
+            (PathBuf::from("/repo/src/a/lib.rs"), GitStatus::Staged),
+            (PathBuf::from("/repo/src/main.rs"), GitStatus::Modified),
+            (PathBuf::from("/repo/src/target"), GitStatus::Ignored),
+        ];
+
+        let result = super::get_entries_with_parents(Path::new("/repo"), entries);
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[&PathBuf::from("/repo/src")], GitStatus::Modified);
+        assert_eq!(result[&PathBuf::from("/repo/src/a")], GitStatus::Staged);
+        assert_eq!(
+            result[&PathBuf::from("/repo/src/target")],
+            GitStatus::Ignored
+        );
+        assert!(!result.contains_key(Path::new("/repo")));
+    }
+
+    #[test]
+    fn load_status_skips_unavailable_until_changed() {
+        use std::path::PathBuf;
+
+        use crate::{action::Action, model::Model, task::Task};
+
+        let path = PathBuf::from("/plain");
+        let mut model = Model::default();
+        model.files.current.path = path.clone();
+
+        assert_eq!(super::load_status(&model, &path).len(), 1);
+
+        super::update_on_unavailable(&mut model, path.clone());
+        assert!(super::load_status(&model, &path).is_empty());
+
+        let actions = super::refresh_status(&mut model, &[path.join(".git")]);
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::LoadGitStatus(target))] if target == &path
+        ));
+        assert_eq!(super::load_status(&model, &path).len(), 1);
+    }
+}
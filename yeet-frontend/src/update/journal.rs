@@ -0,0 +1,260 @@
+use std::time;
+
+use crate::{
+    action::Action,
+    event::Message,
+    model::{
+        journal::{Journal, JournalEntry, JournalGroup, JOURNAL_SIZE},
+        Model,
+    },
+    task::{ConflictPolicy, Task},
+};
+
+use super::junkyard::trash_to_junkyard;
+
+// NOTE: every save is recorded as one group to enable reverting all its operations at once
+pub fn add_group(journal: &mut Journal, actions: &[Action]) -> Vec<Action> {
+    let id = match time::SystemTime::now().duration_since(time::UNIX_EPOCH) {
+        Ok(time) => time.as_millis(),
+        Err(_) => 0,
+    };
+
+    let mut entries = Vec::new();
+    for action in actions {
+        let task = match action {
+            Action::Task(it) => it,
+            _ => continue,
+        };
+
+        let task_entries = get_entries(task);
+        if task_entries.is_empty() {
+            continue;
+        }
+
+        journal
+            .pending
+            .insert(task.to_string(), (id.to_string(), task_entries.clone()));
+
+        entries.extend(task_entries);
+    }
+
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    journal.groups.push(JournalGroup {
+        id: id.to_string(),
+        entries,
+    });
+
+    if journal.groups.len() > JOURNAL_SIZE {
+        journal.groups.remove(0);
+    }
+
+    vec![Action::Task(Task::SaveJournal(journal.clone()))]
+}
+
+fn get_entries(task: &Task) -> Vec<JournalEntry> {
+    match task {
+        Task::AddPath(path) => vec![JournalEntry::Added(path.clone())],
+        Task::DeletePath(path) => vec![JournalEntry::Deleted(path.clone())],
+        Task::RenamePath(old, new, _) => vec![JournalEntry::Renamed(old.clone(), new.clone())],
+        Task::RenamePaths(renames) => renames
+            .iter()
+            .map(|(old, new)| JournalEntry::Renamed(old.clone(), new.clone()))
+            .collect(),
+        Task::TrashPath(entry) => vec![JournalEntry::Trashed(entry.clone())],
+        _ => Vec::new(),
+    }
+}
+
+pub fn complete_task(journal: &mut Journal, identifier: &str) {
+    journal.pending.remove(identifier);
+}
+
+// NOTE: failed operations did not change anything, thus reverting them would hit foreign paths
+pub fn discard_task(journal: &mut Journal, identifier: &str) -> Vec<Action> {
+    let (group_id, entries) = match journal.pending.remove(identifier) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    if let Some(group) = journal.groups.iter_mut().find(|group| group.id == group_id) {
+        for entry in entries {
+            if let Some(index) = group.entries.iter().position(|e| e == &entry) {
+                group.entries.remove(index);
+            }
+        }
+    }
+
+    journal.groups.retain(|group| !group.entries.is_empty());
+
+    vec![Action::Task(Task::SaveJournal(journal.clone()))]
+}
+
+pub fn undo(model: &mut Model) -> Vec<Action> {
+    let group = match model.journal.groups.pop() {
+        Some(it) => it,
+        None => {
+            return vec![Action::EmitMessages(vec![Message::Error(
+                "operation journal is empty".to_owned(),
+            )])]
+        }
+    };
+
+    let mut actions = Vec::new();
+    let mut additions = Vec::new();
+    let mut renames = Vec::new();
+    let mut failures = Vec::new();
+
+    // NOTE: reverting in reverse order keeps chained renames (like bulk renames) consistent
+    for entry in group.entries.into_iter().rev() {
+        match entry {
+            JournalEntry::Added(path) => additions.push(path),
+            JournalEntry::Deleted(path) => failures.push(path),
+            JournalEntry::Renamed(old, new) => renames.push((new, old)),
+            JournalEntry::Trashed(entry) => match entry.target.parent() {
+                Some(parent) if entry.cache.exists() => {
+                    let parent = parent.to_path_buf();
+                    actions.push(Action::Task(Task::RestorePath(
+                        entry,
+                        parent,
+                        ConflictPolicy::Fail,
+                    )));
+                }
+                _ => failures.push(entry.target),
+            },
+        }
+    }
+
+    if !renames.is_empty() {
+        actions.push(Action::Task(Task::RenamePaths(renames)));
+    }
+
+    // NOTE: added paths are trashed instead of deleted to keep content created since the save
+    if !additions.is_empty() {
+        let (transaction, obsolete) = trash_to_junkyard(&mut model.junk, additions);
+        for entry in transaction.entries {
+            actions.push(Action::Task(Task::TrashPath(entry)));
+        }
+
+        if let Some(obsolete) = obsolete {
+            for entry in obsolete.entries {
+                actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+            }
+        }
+    }
+
+    actions.push(Action::Task(Task::SaveJournal(model.journal.clone())));
+
+    if !failures.is_empty() {
+        let paths: Vec<_> = failures
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        actions.push(Action::EmitMessages(vec![Message::Error(format!(
+            "unable to restore deleted path(s): {}",
+            paths.join(", ")
+        ))]));
+    }
+
+    actions
+}
+
+mod test {
+    #[test]
+    fn undo_reverts_last_group() {
+        use std::path::PathBuf;
+
+        use crate::{
+            action::Action,
+            model::Model,
+            task::{ConflictPolicy, Task},
+        };
+
+        let mut model = Model::default();
+        let _ = super::add_group(
+            &mut model.journal,
+            &[Action::Task(Task::AddPath(PathBuf::from("/first")))],
+        );
+        let _ = super::add_group(
+            &mut model.journal,
+            &[
+                Action::Task(Task::RenamePath(
+                    PathBuf::from("/a"),
+                    PathBuf::from("/b"),
+                    ConflictPolicy::Fail,
+                )),
+                Action::Task(Task::RenamePaths(vec![
+                    (PathBuf::from("/c"), PathBuf::from("/tmp")),
+                    (PathBuf::from("/d"), PathBuf::from("/c")),
+                ])),
+                Action::Task(Task::DeletePath(PathBuf::from("/e"))),
+            ],
+        );
+        assert_eq!(2, model.journal.groups.len());
+
+        let actions = super::undo(&mut model);
+        assert_eq!(1, model.journal.groups.len());
+
+        let renames = actions.iter().find_map(|action| match action {
+            Action::Task(Task::RenamePaths(renames)) => Some(renames.clone()),
+            _ => None,
+        });
+        assert_eq!(
+            Some(vec![
+                (PathBuf::from("/c"), PathBuf::from("/d")),
+                (PathBuf::from("/tmp"), PathBuf::from("/c")),
+                (PathBuf::from("/b"), PathBuf::from("/a")),
+            ]),
+            renames
+        );
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, Action::EmitMessages(_))));
+    }
+
+    #[test]
+    fn discard_task_drops_failed_entries() {
+        use std::path::PathBuf;
+
+        use crate::{
+            action::Action,
+            model::Model,
+            task::{ConflictPolicy, Task},
+        };
+
+        let added = Task::AddPath(PathBuf::from("/existing"));
+        let renamed = Task::RenamePath(
+            PathBuf::from("/a"),
+            PathBuf::from("/b"),
+            ConflictPolicy::Fail,
+        );
+
+        let mut model = Model::default();
+        let actions = super::add_group(
+            &mut model.journal,
+            &[Action::Task(added), Action::Task(renamed)],
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::SaveJournal(_))]
+        ));
+
+        let added = Task::AddPath(PathBuf::from("/existing")).to_string();
+        super::discard_task(&mut model.journal, &added);
+        assert_eq!(1, model.journal.groups[0].entries.len());
+
+        let renamed = Task::RenamePath(
+            PathBuf::from("/a"),
+            PathBuf::from("/b"),
+            ConflictPolicy::Fail,
+        )
+        .to_string();
+        super::complete_task(&mut model.journal, &renamed);
+        super::discard_task(&mut model.journal, &renamed);
+        assert_eq!(1, model.journal.groups[0].entries.len());
+        assert!(model.journal.pending.is_empty());
+    }
+}
@@ -4,15 +4,20 @@ use std::{
     time,
 };
 
+use yeet_buffer::{message::BufferMessage, model::Mode};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
 use crate::{
-    action::Action,
+    action::{self, Action},
     model::{
         junkyard::{FileEntry, FileEntryStatus, FileEntryType, FileTransaction, JunkYard},
-        Model,
+        CommandLinePrompt, Model,
     },
-    task::Task,
+    task::{ConflictPolicy, Task},
 };
 
+use super::{commandline::print_in_commandline, selection::get_visual_selected_paths};
+
 pub fn add_to_junkyard(model: &mut Model, paths: &Vec<PathBuf>) -> Vec<Action> {
     let mut actions = Vec::new();
     for path in paths {
@@ -134,26 +139,96 @@ fn decompose_compression_path(path: &Path) -> Option<(String, String, PathBuf)>
 }
 
 pub fn paste_to_junkyard(model: &mut Model, entry_id: &char) -> Vec<Action> {
-    if let Some(transaction) = get_junkyard_transaction(&model.junk, entry_id) {
-        let mut actions = Vec::new();
-        for entry in transaction.entries.iter() {
-            actions.push(Action::Task(Task::RestorePath(
-                entry.clone(),
-                model.files.current.path.clone(),
-            )));
+    let transaction = match get_junkyard_transaction(&model.junk, entry_id) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    let path = model.files.current.path.clone();
+    let conflicts: Vec<_> = transaction
+        .entries
+        .iter()
+        .filter_map(|entry| entry.target.file_name())
+        .filter(|name| path.join(name).exists())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+
+    let entries = transaction.entries.clone();
+    if conflicts.is_empty() {
+        return restore_entries(entries, path, ConflictPolicy::Fail);
+    }
+
+    tracing::info!("paste conflicts with existing paths: {:?}", conflicts);
+
+    let mut content = vec![PrintContent::Default(format!(
+        "{} path(s) already exist:",
+        conflicts.len()
+    ))];
+    content.extend(conflicts.into_iter().map(PrintContent::Default));
+    content.push(PrintContent::Information(
+        "[o]verwrite, [s]kip, [a]uto-rename or [c]ancel".to_string(),
+    ));
+
+    model.commandline.prompt = Some(CommandLinePrompt::PasteConflict(entries, path));
+    print_in_commandline(model, &content)
+}
+
+pub fn resolve_paste_conflict(entries: Vec<FileEntry>, path: PathBuf, answer: &str) -> Vec<Action> {
+    let policy = match answer {
+        "o" => ConflictPolicy::Overwrite,
+        "s" => ConflictPolicy::Skip,
+        "a" => ConflictPolicy::Rename,
+        _ => {
+            tracing::info!("paste canceled with answer {:?}", answer);
+            return Vec::new();
         }
-        actions
+    };
+
+    restore_entries(entries, path, policy)
+}
+
+fn restore_entries(entries: Vec<FileEntry>, path: PathBuf, policy: ConflictPolicy) -> Vec<Action> {
+    entries
+        .into_iter()
+        .map(|entry| Action::Task(Task::RestorePath(entry, path.clone(), policy.clone())))
+        .collect()
+}
+
+pub fn yank_to_junkyard(model: &mut Model, repeat: &usize) -> Vec<Action> {
+    let is_visual = model.mode == Mode::Visual;
+    let paths = if is_visual {
+        get_visual_selected_paths(model)
     } else {
-        Vec::new()
+        get_repeated_paths(model, repeat)
+    };
+
+    let mut actions = Vec::new();
+    if !paths.is_empty() {
+        let (transaction, obsolete) = yank(&mut model.junk, paths);
+        for entry in transaction.entries {
+            actions.push(Action::Task(Task::YankPath(entry)));
+        }
+
+        if let Some(obsolete) = obsolete {
+            for entry in obsolete.entries {
+                actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
+            }
+        }
     }
+
+    if is_visual {
+        actions.push(action::emit_keymap(KeymapMessage::Buffer(
+            BufferMessage::ChangeMode(Mode::Visual, Mode::Navigation),
+        )));
+    }
+
+    actions
 }
 
-pub fn yank_to_junkyard(model: &mut Model, repeat: &usize) -> Vec<Action> {
+fn get_repeated_paths(model: &Model, repeat: &usize) -> Vec<PathBuf> {
     let current_buffer = &model.files.current.buffer;
-    if current_buffer.lines.is_empty() {
-        Vec::new()
-    } else if let Some(cursor) = &model.files.current_cursor {
-        let mut paths = Vec::new();
+    let mut paths = Vec::new();
+    if let Some(cursor) = &model.files.current_cursor {
         for rpt in 0..*repeat {
             let line_index = cursor.vertical_index + rpt;
             if let Some(line) = current_buffer.lines.get(line_index) {
@@ -166,23 +241,9 @@ pub fn yank_to_junkyard(model: &mut Model, repeat: &usize) -> Vec<Action> {
                 paths.push(target);
             }
         }
-
-        let mut actions = Vec::new();
-        let (transaction, obsolete) = yank(&mut model.junk, paths);
-        for entry in transaction.entries {
-            actions.push(Action::Task(Task::YankPath(entry)));
-        }
-
-        if let Some(obsolete) = obsolete {
-            for entry in obsolete.entries {
-                actions.push(Action::Task(Task::DeleteJunkYardEntry(entry)));
-            }
-        }
-
-        actions
-    } else {
-        Vec::new()
     }
+
+    paths
 }
 
 fn yank(
@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, path::Path};
+use std::path::Path;
 
 use yeet_buffer::{
     message::BufferMessage,
@@ -15,15 +15,16 @@ use crate::{
 
 use self::{
     commandline::{
-        leave_commandline, print_in_commandline, update_commandline, update_commandline_on_execute,
-        update_commandline_on_modification,
+        complete_commandline, leave_commandline, navigate_commandline_history,
+        open_commandline_history, print_in_commandline, update_commandline,
+        update_commandline_on_execute, update_commandline_on_modification,
     },
     cursor::move_cursor,
     enumeration::{update_on_enumeration_change, update_on_enumeration_finished},
     junkyard::{add_to_junkyard, paste_to_junkyard, yank_to_junkyard},
     mark::{add_mark, delete_mark},
     mode::{change_mode, set_mode_in_commandline, set_recording_in_commandline},
-    modification::modify_buffer,
+    modification::{modify_buffer, modify_visual_selection, undo_or_redo_buffer},
     navigation::{
         navigate_to_mark, navigate_to_parent, navigate_to_path, navigate_to_path_as_preview,
         navigate_to_selected,
@@ -34,18 +35,23 @@ use self::{
     register::{
         finish_register_scope, replay_macro_register, replay_register, start_register_scope,
     },
-    save::persist_path_changes,
+    save::{persist_path_changes, quit},
     search::clear_search,
     selection::copy_current_selected_path_to_clipboard,
     settings::update_with_settings,
     viewport::{move_viewport, set_viewport_dimensions},
 };
 
+mod bulkrename;
 mod command;
 pub mod commandline;
 mod cursor;
 mod enumeration;
+mod filter;
+mod finder;
+mod git;
 pub mod history;
+mod journal;
 pub mod junkyard;
 mod mark;
 mod mode;
@@ -53,6 +59,7 @@ mod modification;
 mod navigation;
 mod open;
 mod path;
+mod preview;
 mod qfix;
 mod register;
 mod save;
@@ -60,16 +67,11 @@ mod search;
 mod selection;
 mod settings;
 mod sign;
+mod sort;
 mod task;
+mod validation;
 pub mod viewport;
 
-const SORT: fn(&BufferLine, &BufferLine) -> Ordering = |a, b| {
-    a.content
-        .to_stripped_string()
-        .to_ascii_uppercase()
-        .cmp(&b.content.to_stripped_string().to_ascii_uppercase())
-};
-
 #[tracing::instrument(skip(model))]
 pub fn update_model(model: &mut Model, envelope: Envelope) -> Vec<Action> {
     match &envelope.sequence {
@@ -86,12 +88,13 @@ pub fn update_model(model: &mut Model, envelope: Envelope) -> Vec<Action> {
 
     let sequence = envelope.sequence.clone();
 
-    let actions = envelope
+    let actions: Vec<_> = envelope
         .messages
         .into_iter()
         .flat_map(|message| update_with_message(model, message))
         .collect();
 
+    task::add_pending_operations(model, &actions);
     finish_register_scope(&model.mode, &mut model.register, &sequence, &keymaps);
 
     actions
@@ -110,19 +113,41 @@ fn update_with_message(model: &mut Model, message: Message) -> Vec<Action> {
             print_in_commandline(model, &[PrintContent::Error(error.to_string())])
         }
         Message::FdResult(paths) => qfix::add(model, paths),
+        Message::FinderPathsAdded(root, paths) => finder::update_on_paths_added(model, root, paths),
+        Message::GitStatusLoaded(root, entries) => git::update_on_status(model, root, entries),
+        Message::GitStatusUnavailable(path) => git::update_on_unavailable(model, path),
+        Message::GrepResult(matches) => qfix::add_matches(model, matches),
         Message::Keymap(msg) => update_with_keymap_message(model, &msg),
-        Message::PathRemoved(path) => remove_path(model, &path),
-        Message::PathsAdded(paths) => add_paths(model, &paths)
+        Message::PathRemoved(path) => remove_path(model, &path)
             .into_iter()
-            .chain(add_to_junkyard(model, &paths).into_iter())
+            .chain(git::refresh_status(model, &[path]).into_iter())
             .collect(),
+        Message::PathsAdded(contents) => {
+            let paths: Vec<_> = contents.iter().map(|(path, _)| path.clone()).collect();
+            add_paths(model, &contents)
+                .into_iter()
+                .chain(add_to_junkyard(model, &paths).into_iter())
+                .chain(git::refresh_status(model, &paths).into_iter())
+                .collect()
+        }
         Message::PreviewLoaded(content) => update_preview(model, content),
         Message::Rerender => Vec::new(),
         Message::Resize(x, y) => vec![Action::Resize(x, y)],
         Message::TaskStarted(identifier, cancellation) => {
             task::add(model, identifier, cancellation)
         }
-        Message::TaskEnded(identifier) => task::remove(model, identifier),
+        Message::TaskEnded(identifier) => {
+            journal::complete_task(&mut model.journal, &identifier);
+            task::remove(model, identifier)
+        }
+        Message::TaskFailed(identifier, reason) => {
+            let mut actions = journal::discard_task(&mut model.journal, &identifier);
+            actions.extend(task::fail(model, identifier, reason));
+            actions
+        }
+        Message::TaskProgress(identifier, done, total) => {
+            task::update_progress(model, identifier, done, total)
+        }
         Message::ZoxideResult(path) => navigate_to_path(model, path.as_ref()),
     }
 }
@@ -130,8 +155,10 @@ fn update_with_message(model: &mut Model, message: Message) -> Vec<Action> {
 #[tracing::instrument(skip(model, msg))]
 pub fn update_with_keymap_message(model: &mut Model, msg: &KeymapMessage) -> Vec<Action> {
     match msg {
+        KeymapMessage::AddFinderMatchesToQuickFix => finder::add_matches_to_qfix(model),
         KeymapMessage::Buffer(msg) => update_with_buffer_message(model, msg),
         KeymapMessage::ClearSearchHighlight => clear_search(model),
+        KeymapMessage::CompleteCommandLine(direction) => complete_commandline(model, direction),
         KeymapMessage::DeleteMarks(marks) => delete_mark(model, marks),
         KeymapMessage::ExecuteCommand => update_commandline_on_execute(model),
         KeymapMessage::ExecuteCommandString(command) => command::execute(command, model),
@@ -140,20 +167,46 @@ pub fn update_with_keymap_message(model: &mut Model, msg: &KeymapMessage) -> Vec
         }
         KeymapMessage::ExecuteRegister(register) => replay_register(&mut model.register, register),
         KeymapMessage::LeaveCommandMode => leave_commandline(model),
+        KeymapMessage::NavigateCommandLineHistory(direction) => {
+            navigate_commandline_history(model, direction)
+        }
+        KeymapMessage::NavigateFinder(direction) => finder::navigate(model, direction),
         KeymapMessage::NavigateToMark(char) => navigate_to_mark(char, model),
-        KeymapMessage::NavigateToParent => navigate_to_parent(model),
+        KeymapMessage::NavigateToParent => {
+            if model.files.preview_focused {
+                preview::navigate_to_parent(model)
+            } else {
+                navigate_to_parent(model)
+            }
+        }
         KeymapMessage::NavigateToPath(path) => navigate_to_path(model, path),
         KeymapMessage::NavigateToPathAsPreview(path) => navigate_to_path_as_preview(model, path),
-        KeymapMessage::NavigateToSelected => navigate_to_selected(model),
+        KeymapMessage::NavigateToSelected => {
+            if model.files.preview_focused {
+                preview::navigate_to_selected(model)
+            } else {
+                navigate_to_selected(model)
+            }
+        }
+        KeymapMessage::OpenCommandLineHistory(mode) => open_commandline_history(model, mode),
         KeymapMessage::OpenSelected => open_selected(model),
-        KeymapMessage::PasteFromJunkYard(entry_id) => paste_to_junkyard(model, entry_id),
+        KeymapMessage::PasteFromJunkYard(entry_id) => {
+            // NOTE: only archive listings have entries to extract, other previews keep pasting
+            if model.files.preview_focused && model.files.archive.is_some() {
+                preview::extract_selected_entry(model)
+            } else {
+                paste_to_junkyard(model, entry_id)
+            }
+        }
         KeymapMessage::Print(content) => print_in_commandline(model, content),
         KeymapMessage::ReplayMacro(char) => replay_macro_register(&mut model.register, char),
+        KeymapMessage::ScrollPreview(direction) => preview::scroll(model, direction),
         KeymapMessage::SetMark(char) => add_mark(model, *char),
         KeymapMessage::StartMacro(identifier) => set_recording_in_commandline(model, *identifier),
         KeymapMessage::StopMacro => set_mode_in_commandline(model),
+        KeymapMessage::TogglePreviewFocus => preview::toggle_focus(model),
         KeymapMessage::ToggleQuickFix => toggle_selected_to_qfix(model),
-        KeymapMessage::Quit(mode) => vec![Action::Quit(mode.clone(), None)],
+        KeymapMessage::Quit(mode) => quit(model, mode),
         KeymapMessage::YankPathToClipboard => copy_current_selected_path_to_clipboard(model),
         KeymapMessage::YankToJunkYard(repeat) => yank_to_junkyard(model, repeat),
     }
@@ -167,14 +220,23 @@ pub fn update_with_buffer_message(model: &mut Model, msg: &BufferMessage) -> Vec
             Mode::Command(_) => update_commandline_on_modification(model, repeat, modification),
             Mode::Insert | Mode::Normal => modify_buffer(model, repeat, modification),
             Mode::Navigation => Vec::new(),
+            Mode::Visual => modify_visual_selection(model, modification),
         },
         BufferMessage::MoveCursor(rpt, mtn) => match &model.mode {
             Mode::Command(_) => update_commandline(model, Some(msg)),
-            Mode::Insert | Mode::Navigation | Mode::Normal => move_cursor(model, rpt, mtn),
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
+                move_cursor(model, rpt, mtn)
+            }
         },
         BufferMessage::MoveViewPort(mtn) => match model.mode {
             Mode::Command(_) => update_commandline(model, Some(msg)),
-            Mode::Insert | Mode::Navigation | Mode::Normal => move_viewport(model, mtn),
+            Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
+                move_viewport(model, mtn)
+            }
+        },
+        BufferMessage::Redo | BufferMessage::Undo => match model.mode {
+            Mode::Normal => undo_or_redo_buffer(model, msg),
+            Mode::Command(_) | Mode::Insert | Mode::Navigation | Mode::Visual => Vec::new(),
         },
         BufferMessage::SaveBuffer => persist_path_changes(model),
 
@@ -202,11 +264,42 @@ pub fn update_current(model: &mut Model, message: &BufferMessage) {
     );
 }
 
+// NOTE: while the preview is focused, motions and viewport changes target the preview buffer
+pub fn update_focused(model: &mut Model, message: &BufferMessage) {
+    if !model.files.preview_focused {
+        update_current(model, message);
+        return;
+    }
+
+    if let BufferType::Text(_, buffer) = &mut model.files.preview {
+        let viewport = &mut model.files.preview_vp;
+        set_viewport_dimensions(viewport, &model.layout.preview);
+
+        update_buffer(
+            viewport,
+            &mut model.files.preview_cursor,
+            &model.mode,
+            buffer,
+            message,
+        );
+    }
+}
+
 pub fn update_preview(model: &mut Model, content: Preview) -> Vec<Action> {
+    let is_focus_kept = match &content {
+        Preview::Content(path, _) => model.files.preview.resolve_path() == Some(path.as_path()),
+        Preview::Image(_, _) | Preview::None(_) => false,
+    };
+
+    if !is_focus_kept {
+        preview::unfocus(model);
+    }
+
     match content {
         Preview::Content(path, content) => {
             tracing::trace!("updating preview buffer: {:?}", path);
 
+            let content = preview::set_archive_entries(model, &path, content);
             let content = content
                 .iter()
                 .map(|s| BufferLine {
@@ -216,9 +309,22 @@ pub fn update_preview(model: &mut Model, content: Preview) -> Vec<Action> {
                 .collect();
 
             buffer_type(&WindowType::Preview, model, &path, content);
+            qfix::set_preview_to_match(model, &path);
+
+            if model.files.preview_focused {
+                if let Some(cursor) = &mut model.files.preview_cursor {
+                    cursor.hide_cursor_line = false;
+                }
+            }
+        }
+        Preview::Image(path, protocol) => {
+            model.files.archive = None;
+            model.files.preview = BufferType::Image(path, protocol);
+        }
+        Preview::None(_) => {
+            model.files.archive = None;
+            model.files.preview = BufferType::None;
         }
-        Preview::Image(path, protocol) => model.files.preview = BufferType::Image(path, protocol),
-        Preview::None(_) => model.files.preview = BufferType::None,
     };
     Vec::new()
 }
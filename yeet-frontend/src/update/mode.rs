@@ -1,6 +1,6 @@
 use yeet_buffer::{
-    message::BufferMessage,
-    model::{BufferLine, CommandMode, Mode, SearchDirection},
+    message::{BufferMessage, CursorDirection},
+    model::{ansi::Ansi, BufferLine, CommandMode, Mode, SearchDirection},
     update::{focus_buffer, unfocus_buffer, update_buffer},
 };
 use yeet_keymap::message::PrintContent;
@@ -12,16 +12,27 @@ use crate::{
 };
 
 use super::{
-    commandline::print_in_commandline, register::get_macro_register, save::persist_path_changes,
-    viewport::set_viewport_dimensions,
+    command::VISUAL_RANGE, commandline::print_in_commandline, finder, preview,
+    register::get_macro_register, save::persist_path_changes, search::reset_incremental_search,
+    selection::get_visual_selected_paths, viewport::set_viewport_dimensions,
 };
 
 pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
+    // NOTE: keymaps leave visual to navigation, but visual returns to the mode it got entered
+    // from, thus changes made in normal stay uncommited
+    let to = match (from, to) {
+        (Mode::Visual, Mode::Navigation) if model.mode_before == Some(Mode::Normal) => {
+            &Mode::Normal
+        }
+        _ => to,
+    };
+
     match (from, to) {
         (Mode::Command(_), Mode::Command(_))
         | (Mode::Insert, Mode::Insert)
         | (Mode::Navigation, Mode::Navigation)
-        | (Mode::Normal, Mode::Normal) => return Vec::new(),
+        | (Mode::Normal, Mode::Normal)
+        | (Mode::Visual, Mode::Visual) => return Vec::new(),
         _ => {}
     }
 
@@ -32,12 +43,21 @@ pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
     actions.extend(match from {
         Mode::Command(_) => {
             unfocus_buffer(&mut model.commandline.cursor);
-            update_commandline_on_mode_change(model)
+
+            let mut actions = reset_incremental_search(model);
+            actions.extend(finder::close(model));
+            actions.extend(update_commandline_on_mode_change(model));
+            actions
         }
         Mode::Insert | Mode::Navigation | Mode::Normal => {
             unfocus_buffer(&mut model.files.current_cursor);
             vec![]
         }
+        Mode::Visual => {
+            model.files.visual_selection = get_visual_selected_paths(model);
+            unfocus_buffer(&mut model.files.current_cursor);
+            vec![]
+        }
     });
 
     set_commandline_content_to_mode(model);
@@ -49,18 +69,19 @@ pub fn change_mode(model: &mut Model, from: &Mode, to: &Mode) -> Vec<Action> {
             update_commandline_on_mode_change(model)
         }
         Mode::Insert => {
+            preview::unfocus(model);
             focus_buffer(&mut model.files.current_cursor);
             update_current(model, &msg);
             vec![]
         }
         Mode::Navigation => {
-            // TODO: handle file operations: show pending with gray, refresh on operation success
             // TODO: sort and refresh current on PathEnumerationFinished while not in Navigation mode
             focus_buffer(&mut model.files.current_cursor);
             update_current(model, &msg);
             persist_path_changes(model)
         }
-        Mode::Normal => {
+        Mode::Normal | Mode::Visual => {
+            preview::unfocus(model);
             focus_buffer(&mut model.files.current_cursor);
             update_current(model, &msg);
             vec![]
@@ -79,7 +100,11 @@ fn update_commandline_on_mode_change(model: &mut Model) -> Vec<Action> {
 
     let command_mode = match &model.mode {
         Mode::Command(it) => it,
-        Mode::Insert | Mode::Navigation | Mode::Normal => {
+        Mode::Insert | Mode::Navigation | Mode::Normal | Mode::Visual => {
+            commandline.completion = None;
+            commandline.history.index = None;
+            commandline.history.window = false;
+
             let from_command = model
                 .mode_before
                 .as_ref()
@@ -112,11 +137,18 @@ fn update_commandline_on_mode_change(model: &mut Model) -> Vec<Action> {
                 CommandMode::Command => Some(":".to_string()),
                 CommandMode::Search(SearchDirection::Up) => Some("?".to_string()),
                 CommandMode::Search(SearchDirection::Down) => Some("/".to_string()),
-                CommandMode::PrintMultiline => unreachable!(),
+                CommandMode::Find | CommandMode::PrintMultiline => unreachable!(),
+            };
+
+            let from_visual = model.mode_before == Some(Mode::Visual);
+            let content = match &command_mode {
+                CommandMode::Command if from_visual => Ansi::new(VISUAL_RANGE),
+                _ => Ansi::new(""),
             };
 
             let bufferline = BufferLine {
                 prefix,
+                content,
                 ..Default::default()
             };
 
@@ -127,8 +159,18 @@ fn update_commandline_on_mode_change(model: &mut Model) -> Vec<Action> {
                 buffer,
                 &BufferMessage::SetContent(vec![bufferline]),
             );
+
+            if from_visual {
+                update_buffer(
+                    viewport,
+                    &mut commandline.cursor,
+                    &model.mode,
+                    buffer,
+                    &BufferMessage::MoveCursor(1, CursorDirection::LineEnd),
+                );
+            }
         }
-        CommandMode::PrintMultiline => {}
+        CommandMode::Find | CommandMode::PrintMultiline => {}
     };
 
     Vec::new()
@@ -153,3 +195,37 @@ pub fn set_mode_in_commandline(model: &mut Model) -> Vec<Action> {
     print_in_commandline(model, &[PrintContent::Default(content)]);
     Vec::new()
 }
+
+mod test {
+    #[test]
+    fn leave_visual_returns_to_normal_with_uncommited_changes() {
+        use ratatui::layout::Rect;
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+
+        use crate::model::Model;
+
+        let mut model = Model {
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        model.commandline.layout.buffer = Rect::new(0, 0, 80, 1);
+        model.layout.current = Rect::new(0, 0, 80, 10);
+        model.files.current.buffer.lines = vec![BufferLine::from("b")];
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::Content(0, Ansi::new("a"), Ansi::new("b"))],
+        );
+
+        super::change_mode(&mut model, &Mode::Normal, &Mode::Visual);
+        super::change_mode(&mut model, &Mode::Visual, &Mode::Navigation);
+
+        assert_eq!(Mode::Normal, model.mode);
+        assert!(!model
+            .files
+            .current
+            .buffer
+            .undo
+            .get_uncommited_changes()
+            .is_empty());
+    }
+}
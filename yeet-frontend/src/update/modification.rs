@@ -1,7 +1,11 @@
-use yeet_buffer::message::{BufferMessage, TextModification};
+use yeet_buffer::{
+    message::{BufferMessage, TextModification},
+    model::Mode,
+};
+use yeet_keymap::message::KeymapMessage;
 
 use crate::{
-    action::Action,
+    action::{self, Action},
     model::{BufferType, Model},
 };
 
@@ -17,3 +21,36 @@ pub fn modify_buffer(
 
     Vec::new()
 }
+
+pub fn modify_visual_selection(model: &mut Model, modification: &TextModification) -> Vec<Action> {
+    let range = match &mut model.files.current_cursor {
+        Some(cursor) => match cursor.get_visual_range() {
+            Some((start, end)) => {
+                cursor.vertical_index = start;
+                cursor.visual_start = None;
+                Some((start, end))
+            }
+            None => None,
+        },
+        None => None,
+    };
+
+    if let Some((start, end)) = range {
+        let msg = BufferMessage::Modification(end - start + 1, modification.clone());
+        super::update_current(model, &msg);
+
+        model.files.preview = BufferType::None;
+    }
+
+    vec![action::emit_keymap(KeymapMessage::Buffer(
+        BufferMessage::ChangeMode(Mode::Visual, Mode::Normal),
+    ))]
+}
+
+pub fn undo_or_redo_buffer(model: &mut Model, msg: &BufferMessage) -> Vec<Action> {
+    super::update_current(model, msg);
+
+    model.files.preview = BufferType::None;
+
+    Vec::new()
+}
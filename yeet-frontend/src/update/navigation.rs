@@ -3,11 +3,12 @@ use std::{mem, path::Path};
 use yeet_buffer::model::{viewport::ViewPort, Buffer, Cursor, CursorPosition};
 
 use crate::{
-    action::Action,
-    model::{BufferType, Model, WindowType},
+    action::{self, Action},
+    model::{BufferType, FileWindow, Model, WindowType},
+    task::archive,
 };
 
-use super::{history, selection};
+use super::{history, preview, selection};
 
 #[tracing::instrument(skip(model))]
 pub fn navigate_to_mark(char: &char, model: &mut Model) -> Vec<Action> {
@@ -73,6 +74,10 @@ pub fn navigate_to_path_with_selection(
     path: &Path,
     selection: &Option<String>,
 ) -> Vec<Action> {
+    if let Some(error) = get_bulk_rename_error(&model.files) {
+        return vec![error];
+    }
+
     if path.is_file() {
         tracing::warn!("path is a file, not a directory: {:?}", path);
         return Vec::new();
@@ -119,6 +124,10 @@ pub fn navigate_to_path_with_selection(
 
 #[tracing::instrument(skip(model))]
 pub fn navigate_to_parent(model: &mut Model) -> Vec<Action> {
+    if let Some(error) = get_bulk_rename_error(&model.files) {
+        return vec![error];
+    }
+
     if let Some(path) = model.files.current.path.clone().parent() {
         if model.files.current.path == path {
             return Vec::new();
@@ -167,8 +176,19 @@ pub fn navigate_to_parent(model: &mut Model) -> Vec<Action> {
 
 #[tracing::instrument(skip(model))]
 pub fn navigate_to_selected(model: &mut Model) -> Vec<Action> {
+    if let Some(error) = get_bulk_rename_error(&model.files) {
+        return vec![error];
+    }
+
     if let Some(selected) = selection::get_current_selected_path(model) {
-        if model.files.current.path == selected || !selected.is_dir() {
+        if model.files.current.path == selected {
+            return Vec::new();
+        }
+
+        if !selected.is_dir() {
+            if archive::is_archive(&selected) {
+                return preview::focus(model);
+            }
             return Vec::new();
         }
 
@@ -237,6 +257,17 @@ pub fn navigate_to_selected(model: &mut Model) -> Vec<Action> {
     }
 }
 
+// NOTE: navigating replaces the current buffer, which holds the paths of the bulk rename
+fn get_bulk_rename_error(files: &FileWindow) -> Option<Action> {
+    if files.bulk_rename.is_some() {
+        Some(action::emit_error(
+            "navigation is not available while bulk renaming, save with :w or discard with :e!",
+        ))
+    } else {
+        None
+    }
+}
+
 fn mem_swap_viewport(dest_viewport: &mut ViewPort, src_viewport: &mut ViewPort) {
     mem::swap(
         &mut dest_viewport.horizontal_index,
@@ -256,3 +287,28 @@ fn mem_swap_cursor(dest_cursor: &mut Option<Cursor>, src_cursor: &mut Option<Cur
         );
     }
 }
+
+mod test {
+    #[test]
+    fn navigate_while_bulk_renaming() {
+        use std::path::{Path, PathBuf};
+
+        use crate::model::{BulkRename, Model};
+
+        let mut model = Model::default();
+        model.files.current.path = PathBuf::from("/tmp/yeet/current");
+        model.files.bulk_rename = Some(BulkRename {
+            paths: vec![PathBuf::from("/tmp/yeet/current/file")],
+            root: PathBuf::from("/tmp/yeet/current"),
+        });
+
+        let parent = super::navigate_to_parent(&mut model);
+        let path = super::navigate_to_path(&mut model, Path::new("/tmp"));
+        let selected = super::navigate_to_selected(&mut model);
+
+        assert_eq!(1, parent.len());
+        assert_eq!(1, path.len());
+        assert_eq!(1, selected.len());
+        assert_eq!(PathBuf::from("/tmp/yeet/current"), model.files.current.path);
+    }
+}
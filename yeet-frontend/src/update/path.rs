@@ -3,33 +3,70 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use lscolors::LsColors;
 use yeet_buffer::{
     message::BufferMessage,
-    model::{ansi::Ansi, Buffer, BufferLine, Cursor, Mode},
+    model::{
+        undo::{consolidate_modifications, BufferChanged},
+        Buffer, BufferLine, Cursor, Mode,
+    },
     update::update_buffer,
 };
 
 use crate::{
     action::Action,
-    model::{BufferType, Model, WindowType},
+    event::{ContentKind, Message},
+    model::{BufferType, Model, WindowType, ERROR_SIGN_ID, REMOVED_SIGN_ID},
+    task::{column::add_columns, visibility::VisibilityFilter},
 };
 
 use super::{
+    enumeration::from_enumeration,
+    filter::is_filtered_out,
     history::get_selection_from_history,
     junkyard::remove_from_junkyard,
     selection,
-    sign::{set_sign_if_marked, set_sign_if_qfix},
+    sign::{self, set_sign_if_git, set_sign_if_marked, set_sign_if_operation, set_sign_if_qfix},
+    sort::get_sorted_lines,
 };
 
 #[tracing::instrument(skip(model))]
-pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
-    let mut buffer_contents = vec![(
-        model.files.current.path.as_path(),
-        &mut model.files.current_vp,
-        &mut model.files.current_cursor,
-        &mut model.files.current.buffer,
-        model.mode == Mode::Navigation,
-    )];
+pub fn add_paths(model: &mut Model, contents: &[(PathBuf, Option<String>)]) -> Vec<Action> {
+    // NOTE: names renamed or removed in the buffer stay as edited by the user
+    let pending = get_pending_edits(model).unwrap_or_default();
+    let released: Vec<_> = get_released_names(&pending)
+        .iter()
+        .map(|name| model.files.current.path.join(name))
+        .collect();
+
+    let targets: Vec<_> = get_target_names(&pending)
+        .iter()
+        .map(|name| model.files.current.path.join(name))
+        .collect();
+
+    let visibility = model.settings.get_visibility();
+    let contents: Vec<_> = contents
+        .iter()
+        .filter(|(path, _)| !released.contains(path))
+        .filter(|(path, _)| !is_filtered_out(&model.files.filters, path))
+        .filter(|(path, _)| match path.parent() {
+            Some(parent) => {
+                VisibilityFilter::new(&visibility, parent).is_visible(path, path.is_dir())
+            }
+            None => true,
+        })
+        .collect();
+
+    let mut buffer_contents = Vec::new();
+    if model.files.bulk_rename.is_none() {
+        buffer_contents.push((
+            model.files.current.path.as_path(),
+            &mut model.files.current_vp,
+            &mut model.files.current_cursor,
+            &mut model.files.current.buffer,
+            model.mode == Mode::Navigation,
+        ));
+    }
 
     if let BufferType::Text(path, buffer) = &mut model.files.parent {
         buffer_contents.push((
@@ -52,8 +89,12 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
     }
 
     for (path, viewport, cursor, buffer, sort) in buffer_contents {
-        let paths_for_buffer: Vec<_> = paths.iter().filter(|p| p.parent() == Some(path)).collect();
-        if paths_for_buffer.is_empty() {
+        let contents_for_buffer: Vec<_> = contents
+            .iter()
+            .filter(|(p, _)| p.parent() == Some(path))
+            .collect();
+
+        if contents_for_buffer.is_empty() {
             continue;
         }
 
@@ -78,14 +119,24 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
             })
             .collect::<HashMap<_, _>>();
 
-        for path in paths_for_buffer {
+        for (path, columns) in contents_for_buffer {
             if let Some(basename) = path.file_name().and_then(|oss| oss.to_str()) {
-                let mut line = from(path);
+                let mut line = from(&model.settings.ls_colors, path);
+                if let Some(columns) = columns {
+                    add_columns(&mut line, columns);
+                }
                 set_sign_if_marked(&model.marks, &mut line, path);
                 set_sign_if_qfix(&model.qfix, &mut line, path);
+                set_sign_if_git(&model.git, &mut line, path);
+                set_sign_if_operation(&model.operations, &mut line, path);
 
+                // NOTE: lines added by the user keep their content, but are marked as conflict
                 if let Some(index) = indexes.get(basename) {
-                    buffer.lines[*index] = line;
+                    if targets.contains(path) {
+                        sign::set(&mut buffer.lines[*index], ERROR_SIGN_ID);
+                    } else {
+                        buffer.lines[*index] = line;
+                    }
                 } else {
                     buffer.lines.push(line);
                 }
@@ -101,12 +152,14 @@ pub fn add_paths(model: &mut Model, paths: &[PathBuf]) -> Vec<Action> {
         }
 
         if sort {
+            let lines =
+                get_sorted_lines(&model.settings.sort, path, buffer.lines.drain(..).collect());
             update_buffer(
                 viewport,
                 cursor,
                 &model.mode,
                 buffer,
-                &BufferMessage::SortContent(super::SORT),
+                &BufferMessage::SetContent(lines),
             );
         }
 
@@ -137,22 +190,13 @@ fn get_selected_content_from_buffer(cursor: &Cursor, model: &Buffer) -> Option<S
         .map(|line| line.content.to_stripped_string())
 }
 
-fn from(path: &Path) -> BufferLine {
+fn from(ls_colors: &LsColors, path: &Path) -> BufferLine {
     let content = match path.file_name() {
-        Some(content) => content.to_str().unwrap_or("").to_string(),
-        None => "".to_string(),
-    };
-
-    let content = if path.is_dir() {
-        format!("\x1b[94m{}\x1b[39m", content)
-    } else {
-        content
+        Some(content) => content.to_str().unwrap_or(""),
+        None => "",
     };
 
-    BufferLine {
-        content: Ansi::new(&content),
-        ..Default::default()
-    }
+    from_enumeration(ls_colors, content, &ContentKind::from_path(path))
 }
 
 #[tracing::instrument(skip(model))]
@@ -161,17 +205,27 @@ pub fn remove_path(model: &mut Model, path: &Path) -> Vec<Action> {
         remove_from_junkyard(&mut model.junk, path);
     }
 
+    let is_current = path.parent() == Some(model.files.current.path.as_path());
+    if is_current && model.files.bulk_rename.is_none() {
+        if let Some(changes) = get_pending_edits(model) {
+            return mark_removed_path(model, path, &changes);
+        }
+    }
+
     let current_selection = match &model.files.current_cursor {
         Some(it) => get_selected_content_from_buffer(it, &model.files.current.buffer),
         None => None,
     };
 
-    let mut buffer_contents = vec![(
-        model.files.current.path.as_path(),
-        &mut model.files.current_vp,
-        &mut model.files.current_cursor,
-        &mut model.files.current.buffer,
-    )];
+    let mut buffer_contents = Vec::new();
+    if model.files.bulk_rename.is_none() {
+        buffer_contents.push((
+            model.files.current.path.as_path(),
+            &mut model.files.current_vp,
+            &mut model.files.current_cursor,
+            &mut model.files.current.buffer,
+        ));
+    }
 
     if let BufferType::Text(path, buffer) = &mut model.files.parent {
         buffer_contents.push((
@@ -217,7 +271,7 @@ pub fn remove_path(model: &mut Model, path: &Path) -> Vec<Action> {
         }
     }
 
-    if let Some(selection) = current_selection {
+    if let Some(selection) = current_selection.filter(|_| model.files.bulk_rename.is_none()) {
         update_buffer(
             &mut model.files.current_vp,
             &mut model.files.current_cursor,
@@ -235,3 +289,201 @@ pub fn remove_path(model: &mut Model, path: &Path) -> Vec<Action> {
 
     actions
 }
+
+// NOTE: uncommited changes reference lines by index, thus external changes must not insert or
+// remove lines in front of them while the user edits the current buffer
+pub fn get_pending_edits(model: &Model) -> Option<Vec<BufferChanged>> {
+    let changes = model.files.current.buffer.undo.get_uncommited_changes();
+    if model.mode == Mode::Insert || !changes.is_empty() {
+        Some(consolidate_modifications(&changes))
+    } else {
+        None
+    }
+}
+
+pub fn get_released_names(changes: &[BufferChanged]) -> Vec<String> {
+    changes
+        .iter()
+        .filter_map(|change| match change {
+            BufferChanged::Content(_, old, _) => Some(old.to_stripped_string()),
+            BufferChanged::LineRemoved(_, old) => Some(old.content.to_stripped_string()),
+            BufferChanged::LineAdded(_, _) => None,
+        })
+        .collect()
+}
+
+pub fn get_target_names(changes: &[BufferChanged]) -> Vec<String> {
+    changes
+        .iter()
+        .filter_map(|change| match change {
+            BufferChanged::Content(_, _, new) | BufferChanged::LineAdded(_, new) => {
+                Some(new.to_stripped_string())
+            }
+            BufferChanged::LineRemoved(_, _) => None,
+        })
+        .filter(|name| !name.is_empty())
+        .map(|name| name.trim_end_matches('/').to_owned())
+        .collect()
+}
+
+pub fn mark_removed_path(model: &mut Model, path: &Path, changes: &[BufferChanged]) -> Vec<Action> {
+    let basename = match path.file_name().and_then(|oss| oss.to_str()) {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    let renamed = changes.iter().find_map(|change| match change {
+        BufferChanged::Content(_, old, new)
+            if old.to_stripped_string() == basename && !new.is_empty() =>
+        {
+            Some(new.to_stripped_string())
+        }
+        _ => None,
+    });
+
+    let lines = &mut model.files.current.buffer.lines;
+    if let Some(renamed) = renamed {
+        if let Some(line) = lines
+            .iter_mut()
+            .find(|line| line.content.to_stripped_string() == renamed)
+        {
+            sign::set(line, ERROR_SIGN_ID);
+        }
+
+        return vec![Action::EmitMessages(vec![Message::Error(format!(
+            "{} was removed externally, but is renamed to {} in the buffer",
+            path.display(),
+            renamed
+        ))])];
+    }
+
+    let is_edited = changes.iter().any(|change| match change {
+        BufferChanged::Content(_, _, new) | BufferChanged::LineAdded(_, new) => {
+            new.to_stripped_string() == basename
+        }
+        BufferChanged::LineRemoved(_, _) => false,
+    });
+
+    // NOTE: removed lines get dropped on save, which resets the index based undo history
+    if !is_edited {
+        if let Some(line) = lines
+            .iter_mut()
+            .find(|line| line.content.to_stripped_string() == basename)
+        {
+            sign::set(line, REMOVED_SIGN_ID);
+        }
+    }
+
+    Vec::new()
+}
+
+mod test {
+    #[test]
+    fn remove_path_keeps_lines_with_pending_edits() {
+        use std::path::PathBuf;
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+
+        use crate::model::{Model, ERROR_SIGN_ID, REMOVED_SIGN_ID};
+
+        let mut model = Model::default();
+        model.files.current.path = PathBuf::from("/root");
+        model.files.current.buffer.lines = ["a", "renamed", "c"]
+            .iter()
+            .map(|name| BufferLine {
+                content: Ansi::new(name),
+                ..Default::default()
+            })
+            .collect();
+
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::Content(
+                1,
+                Ansi::new("b"),
+                Ansi::new("renamed"),
+            )],
+        );
+
+        super::remove_path(&mut model, &PathBuf::from("/root/a"));
+        let actions = super::remove_path(&mut model, &PathBuf::from("/root/b"));
+        super::add_paths(&mut model, &[(PathBuf::from("/root/b"), None)]);
+
+        let lines = &model.files.current.buffer.lines;
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].signs.iter().any(|s| s.id == REMOVED_SIGN_ID));
+        assert!(lines[1].signs.iter().any(|s| s.id == ERROR_SIGN_ID));
+        assert!(lines[2].signs.is_empty());
+        assert_eq!(actions.len(), 1);
+    }
+
+    #[test]
+    fn add_paths_keeps_added_lines_with_pending_edits() {
+        use std::path::PathBuf;
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+
+        use crate::model::{Model, ERROR_SIGN_ID};
+
+        let mut model = Model {
+            mode: Mode::Normal,
+            ..Default::default()
+        };
+        model.files.current.path = PathBuf::from("/root");
+        model.files.current_vp.height = 10;
+        model.files.current.buffer.lines = ["a", "added"]
+            .iter()
+            .map(|name| BufferLine {
+                content: Ansi::new(name),
+                ..Default::default()
+            })
+            .collect();
+
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::LineAdded(1, Ansi::new("added"))],
+        );
+
+        super::add_paths(
+            &mut model,
+            &[
+                (PathBuf::from("/root/added"), None),
+                (PathBuf::from("/root/b"), None),
+            ],
+        );
+
+        let lines = &model.files.current.buffer.lines;
+        let names: Vec<_> = lines
+            .iter()
+            .map(|line| line.content.to_stripped_string())
+            .collect();
+
+        assert_eq!(names, vec!["a", "added", "b"]);
+        assert!(lines[0].signs.is_empty());
+        assert!(lines[1].signs.iter().any(|s| s.id == ERROR_SIGN_ID));
+        assert!(lines[2].signs.is_empty());
+    }
+
+    #[test]
+    fn add_paths_with_formatted_columns() {
+        use std::path::PathBuf;
+
+        use yeet_buffer::model::Mode;
+
+        use crate::model::Model;
+
+        let mut model = Model::default();
+        model.files.current.path = PathBuf::from("/root");
+        model.files.current_vp.height = 10;
+        model.mode = Mode::Normal;
+
+        super::add_paths(
+            &mut model,
+            &[(PathBuf::from("/root/a"), Some("rw-r--r--".to_owned()))],
+        );
+
+        let lines = &model.files.current.buffer.lines;
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].suffix, Some("rw-r--r--".to_owned()));
+    }
+}
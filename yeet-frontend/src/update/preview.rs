@@ -0,0 +1,329 @@
+use std::path::Path;
+
+use yeet_buffer::{
+    message::{BufferMessage, LineDirection},
+    model::{ansi::Ansi, BufferLine},
+};
+
+use crate::{
+    action::Action,
+    model::{ArchiveListing, BufferType, Model, WindowType},
+    task::{archive, Task},
+};
+
+use super::{buffer_type, update_focused, viewport::set_viewport_dimensions};
+
+pub fn scroll(model: &mut Model, direction: &LineDirection) -> Vec<Action> {
+    let buffer = match &model.files.preview {
+        BufferType::Text(_, buffer) => buffer,
+        BufferType::Image(_, _) | BufferType::None => return Vec::new(),
+    };
+
+    let viewport = &mut model.files.preview_vp;
+    set_viewport_dimensions(viewport, &model.layout.preview);
+
+    let max_index = buffer.lines.len().saturating_sub(viewport.height);
+    viewport.vertical_index = match direction {
+        LineDirection::Up => viewport.vertical_index.saturating_sub(1),
+        LineDirection::Down => (viewport.vertical_index + 1).min(max_index),
+    };
+
+    // NOTE: like C-e and C-y in vim, the cursor gets dragged along to stay visible
+    if model.files.preview_focused {
+        if let Some(cursor) = &mut model.files.preview_cursor {
+            let last_visible = (viewport.vertical_index + viewport.height).saturating_sub(1);
+            cursor.vertical_index = cursor
+                .vertical_index
+                .min(last_visible)
+                .max(viewport.vertical_index);
+        }
+    }
+
+    Vec::new()
+}
+
+pub fn toggle_focus(model: &mut Model) -> Vec<Action> {
+    if model.files.preview_focused {
+        unfocus(model);
+        Vec::new()
+    } else {
+        focus(model)
+    }
+}
+
+pub fn focus(model: &mut Model) -> Vec<Action> {
+    // NOTE: directory previews get entered with l, thus only file content is focusable
+    match &model.files.preview {
+        BufferType::Text(path, _) if !path.is_dir() => {}
+        BufferType::Text(_, _) | BufferType::Image(_, _) | BufferType::None => return Vec::new(),
+    }
+
+    model.files.preview_focused = true;
+
+    let vertical_index = model.files.preview_vp.vertical_index;
+    let cursor = model
+        .files
+        .preview_cursor
+        .get_or_insert_with(Default::default);
+
+    if cursor.hide_cursor_line {
+        cursor.vertical_index = vertical_index;
+        cursor.hide_cursor_line = false;
+    }
+
+    Vec::new()
+}
+
+// NOTE: archive previews list all entries, the buffer only shows the level below the prefix
+pub fn set_archive_entries(model: &mut Model, path: &Path, entries: Vec<String>) -> Vec<String> {
+    if !archive::is_archive(path) {
+        model.files.archive = None;
+        return entries;
+    }
+
+    let prefix = match model.files.archive.take() {
+        Some(listing) if listing.path == path => listing.prefix,
+        Some(_) | None => String::new(),
+    };
+
+    let level = get_archive_level(&entries, &prefix);
+    model.files.archive = Some(ArchiveListing {
+        entries,
+        path: path.to_path_buf(),
+        prefix,
+    });
+
+    level
+}
+
+fn get_archive_level(entries: &[String], prefix: &str) -> Vec<String> {
+    let mut level: Vec<String> = Vec::new();
+    for entry in entries {
+        let remaining = match entry.strip_prefix(prefix) {
+            Some(it) if !it.is_empty() => it,
+            Some(_) | None => continue,
+        };
+
+        let name = match remaining.split_once('/') {
+            Some((directory, _)) => format!("{}/", directory),
+            None => remaining.to_owned(),
+        };
+
+        if !level.contains(&name) {
+            level.push(name);
+        }
+    }
+
+    level.sort_by(|a, b| (!a.ends_with('/'), a).cmp(&(!b.ends_with('/'), b)));
+    level
+}
+
+// NOTE: a focused archive preview acts as read-only directory, p extracts the selected entry
+pub fn extract_selected_entry(model: &mut Model) -> Vec<Action> {
+    let (path, entry) = match (get_selected_line(model), &model.files.archive) {
+        (Some(line), Some(listing)) => {
+            (listing.path.clone(), format!("{}{}", listing.prefix, line))
+        }
+        (_, _) => return Vec::new(),
+    };
+
+    vec![Action::Task(Task::ExtractArchiveEntry(
+        path,
+        entry,
+        model.files.current.path.clone(),
+    ))]
+}
+
+pub fn navigate_to_selected(model: &mut Model) -> Vec<Action> {
+    let selected = match get_selected_line(model) {
+        Some(it) if it.ends_with('/') => it,
+        Some(_) | None => return Vec::new(),
+    };
+
+    if let Some(listing) = &mut model.files.archive {
+        listing.prefix.push_str(&selected);
+        set_archive_level(model, None);
+    }
+
+    Vec::new()
+}
+
+// NOTE: on the archive root, h leaves the preview focus like for any other preview
+pub fn navigate_to_parent(model: &mut Model) -> Vec<Action> {
+    let listing = match &mut model.files.archive {
+        Some(it) if !it.prefix.is_empty() => it,
+        Some(_) | None => {
+            unfocus(model);
+            return Vec::new();
+        }
+    };
+
+    let trimmed = listing.prefix.trim_end_matches('/');
+    let (parent, selection) = match trimmed.rsplit_once('/') {
+        Some((parent, directory)) => (format!("{}/", parent), format!("{}/", directory)),
+        None => (String::new(), format!("{}/", trimmed)),
+    };
+
+    listing.prefix = parent;
+    set_archive_level(model, Some(selection));
+
+    Vec::new()
+}
+
+fn set_archive_level(model: &mut Model, selection: Option<String>) {
+    let (path, level) = match &model.files.archive {
+        Some(listing) => (
+            listing.path.clone(),
+            get_archive_level(&listing.entries, &listing.prefix),
+        ),
+        None => return,
+    };
+
+    let lines = level
+        .iter()
+        .map(|name| BufferLine {
+            content: Ansi::new(name),
+            ..Default::default()
+        })
+        .collect();
+
+    buffer_type(&WindowType::Preview, model, &path, lines);
+    if let Some(cursor) = &mut model.files.preview_cursor {
+        cursor.hide_cursor_line = false;
+    }
+
+    if let Some(selection) = selection {
+        update_focused(model, &BufferMessage::SetCursorToLineContent(selection));
+    }
+}
+
+fn get_selected_line(model: &Model) -> Option<String> {
+    let buffer = match &model.files.preview {
+        BufferType::Text(_, buffer) => buffer,
+        BufferType::Image(_, _) | BufferType::None => return None,
+    };
+
+    model
+        .files
+        .preview_cursor
+        .as_ref()
+        .and_then(|cursor| buffer.lines.get(cursor.vertical_index))
+        .map(|line| line.content.to_stripped_string())
+}
+
+pub fn unfocus(model: &mut Model) {
+    if !model.files.preview_focused {
+        return;
+    }
+
+    model.files.preview_focused = false;
+    if let Some(cursor) = &mut model.files.preview_cursor {
+        cursor.hide_cursor_line = true;
+    }
+}
+
+mod test {
+    #[test]
+    fn scroll_stops_at_last_page_and_drags_focused_cursor() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+        use yeet_buffer::{
+            message::LineDirection,
+            model::{Buffer, BufferLine},
+        };
+
+        use crate::model::{BufferType, Model};
+
+        let mut model = Model::default();
+        model.layout.preview = Rect::new(0, 0, 20, 2);
+        model.files.preview = BufferType::Text(
+            PathBuf::from("/file.txt"),
+            Buffer {
+                lines: vec![BufferLine::default(); 4],
+                ..Default::default()
+            },
+        );
+
+        super::toggle_focus(&mut model);
+        assert!(model.files.preview_focused);
+
+        for _ in 0..5 {
+            super::scroll(&mut model, &LineDirection::Down);
+        }
+        assert_eq!(2, model.files.preview_vp.vertical_index);
+
+        let vertical_index = model
+            .files
+            .preview_cursor
+            .as_ref()
+            .map(|c| c.vertical_index);
+        assert_eq!(Some(2), vertical_index);
+
+        super::scroll(&mut model, &LineDirection::Up);
+        assert_eq!(1, model.files.preview_vp.vertical_index);
+
+        super::toggle_focus(&mut model);
+        assert!(!model.files.preview_focused);
+    }
+
+    #[test]
+    fn navigate_archive_levels() {
+        use std::path::PathBuf;
+
+        use ratatui::layout::Rect;
+
+        use crate::{
+            action::Action,
+            event::Preview,
+            model::{BufferType, Model},
+            task::Task,
+        };
+
+        let get_lines = |model: &Model| -> Vec<String> {
+            match &model.files.preview {
+                BufferType::Text(_, buffer) => buffer
+                    .lines
+                    .iter()
+                    .map(|line| line.content.to_stripped_string())
+                    .collect(),
+                BufferType::Image(_, _) | BufferType::None => Vec::new(),
+            }
+        };
+
+        let mut model = Model::default();
+        model.layout.preview = Rect::new(0, 0, 20, 10);
+
+        let entries = ["dir/", "dir/sub/", "dir/sub/file", "dir/inner", "top"]
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect();
+
+        let path = PathBuf::from("/archive.tar");
+        crate::update::update_preview(&mut model, Preview::Content(path, entries));
+        assert_eq!(vec!["dir/", "top"], get_lines(&model));
+
+        super::focus(&mut model);
+        super::navigate_to_selected(&mut model);
+        assert_eq!(vec!["sub/", "inner"], get_lines(&model));
+
+        super::navigate_to_selected(&mut model);
+        assert_eq!(vec!["file"], get_lines(&model));
+
+        let actions = super::extract_selected_entry(&mut model);
+        assert!(matches!(
+            actions.as_slice(),
+            [Action::Task(Task::ExtractArchiveEntry(_, entry, _))] if entry == "dir/sub/file"
+        ));
+
+        super::navigate_to_parent(&mut model);
+        assert_eq!(vec!["sub/", "inner"], get_lines(&model));
+
+        super::navigate_to_parent(&mut model);
+        assert_eq!(vec!["dir/", "top"], get_lines(&model));
+        assert!(model.files.preview_focused);
+
+        super::navigate_to_parent(&mut model);
+        assert!(!model.files.preview_focused);
+    }
+}
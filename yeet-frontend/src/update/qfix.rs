@@ -1,20 +1,39 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use yeet_buffer::{
+    message::{BufferMessage, ViewPortDirection},
+    model::Mode,
+    update::update_buffer,
+};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
 
 use crate::{
-    action::Action,
-    model::{qfix::QFIX_SIGN_ID, Model},
+    action::{self, Action},
+    model::{
+        qfix::{QuickFixMatch, QFIX_SIGN_ID},
+        BufferType, Model,
+    },
 };
 
 use super::{
-    selection::{get_current_selected_bufferline, get_current_selected_path},
+    selection::{
+        get_current_selected_bufferline, get_current_selected_path, get_visual_selected_paths,
+    },
     sign,
 };
 
 pub fn toggle_selected_to_qfix(model: &mut Model) -> Vec<Action> {
+    if model.mode == Mode::Visual {
+        return toggle_visual_selection_to_qfix(model);
+    }
+
     let selected = get_current_selected_path(model);
     if let Some(selected) = selected {
         if model.qfix.entries.contains(&selected) {
-            model.qfix.entries.retain(|p| p != &selected);
+            model.qfix.remove(&selected);
             if let Some(bl) = get_current_selected_bufferline(model) {
                 sign::unset(bl, QFIX_SIGN_ID);
             }
@@ -28,12 +47,100 @@ pub fn toggle_selected_to_qfix(model: &mut Model) -> Vec<Action> {
     Vec::new()
 }
 
+fn toggle_visual_selection_to_qfix(model: &mut Model) -> Vec<Action> {
+    for path in get_visual_selected_paths(model) {
+        if model.qfix.entries.contains(&path) {
+            model.qfix.remove(&path);
+            sign::unset_sign_for_path(model, path.as_path(), QFIX_SIGN_ID);
+        } else {
+            sign::set_sign_for_path(model, path.as_path(), QFIX_SIGN_ID);
+            model.qfix.entries.push(path);
+        }
+    }
+
+    vec![action::emit_keymap(KeymapMessage::Buffer(
+        BufferMessage::ChangeMode(Mode::Visual, Mode::Navigation),
+    ))]
+}
+
 pub fn add(model: &mut Model, paths: Vec<PathBuf>) -> Vec<Action> {
+    let mut entries: HashSet<_> = model.qfix.entries.iter().cloned().collect();
     for path in paths {
-        if !model.qfix.entries.contains(&path) {
+        if entries.insert(path.clone()) {
             sign::set_sign_for_path(model, path.as_path(), QFIX_SIGN_ID);
             model.qfix.entries.push(path);
         };
     }
     Vec::new()
 }
+
+pub fn add_matches(model: &mut Model, matches: Vec<(PathBuf, usize, String)>) -> Vec<Action> {
+    let count = matches.len();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut replaced = HashSet::new();
+    for (path, line_number, text) in matches {
+        // NOTE: matches of a previous grep get replaced to keep the lines of the latest search
+        if replaced.insert(path.clone()) {
+            model.qfix.matches.insert(path.clone(), Vec::new());
+            paths.push(path.clone());
+        }
+
+        model
+            .qfix
+            .matches
+            .entry(path)
+            .or_default()
+            .push(QuickFixMatch { line_number, text });
+    }
+
+    let files = paths.len();
+    let mut actions = add(model, paths);
+    actions.push(action::emit_keymap(KeymapMessage::Print(vec![
+        PrintContent::Information(format!("{} lines in {} files matched", count, files)),
+    ])));
+
+    actions
+}
+
+pub fn set_preview_to_match(model: &mut Model, path: &Path) {
+    if model
+        .qfix
+        .entries
+        .get(model.qfix.current_index)
+        .map(|p| p.as_path())
+        != Some(path)
+    {
+        return;
+    }
+
+    let current_match = model.qfix.current_match;
+    let line_number = match model
+        .qfix
+        .matches
+        .get(path)
+        .and_then(|it| it.get(current_match))
+    {
+        Some(it) => it.line_number,
+        None => return,
+    };
+
+    let buffer = match &mut model.files.preview {
+        BufferType::Text(_, buffer) => buffer,
+        BufferType::Image(_, _) | BufferType::None => return,
+    };
+
+    if let Some(cursor) = &mut model.files.preview_cursor {
+        cursor.vertical_index = line_number
+            .saturating_sub(1)
+            .min(buffer.lines.len().saturating_sub(1));
+        cursor.hide_cursor_line = false;
+    }
+
+    update_buffer(
+        &mut model.files.preview_vp,
+        &mut model.files.preview_cursor,
+        &model.mode,
+        buffer,
+        &BufferMessage::MoveViewPort(ViewPortDirection::CenterOnCursor),
+    );
+}
@@ -1,22 +1,134 @@
+use std::path::{Path, PathBuf};
+
 use yeet_buffer::{
     message::BufferMessage,
     model::{
         undo::{consolidate_modifications, BufferChanged},
-        BufferResult,
+        BufferResult, Mode,
     },
     update::update_buffer,
 };
+use yeet_keymap::message::{PrintContent, QuitMode};
+
+use crate::{
+    action::{self, Action},
+    model::{CommandLinePrompt, FileWindow, Model, REMOVED_SIGN_ID},
+    task::{ConflictPolicy, Task},
+};
 
-use crate::{action::Action, model::Model, task::Task};
+use super::{
+    bulkrename, commandline::print_in_commandline, journal, junkyard::trash_to_junkyard,
+    selection::get_current_selected_bufferline, validation,
+};
 
-use super::{junkyard::trash_to_junkyard, selection::get_current_selected_bufferline};
+// NOTE: guards commands which replace the current buffer and thus would drop unsaved changes
+pub fn get_unsaved_changes_error(files: &FileWindow) -> Option<Action> {
+    let changes = files.current.buffer.undo.get_uncommited_changes();
+    if changes.is_empty() {
+        None
+    } else {
+        Some(action::emit_error(
+            "current directory has unsaved changes, save with :w or revert them with u",
+        ))
+    }
+}
 
 #[tracing::instrument(skip(model))]
 pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
+    if model.files.bulk_rename.is_some() {
+        return bulkrename::persist(model);
+    }
+
+    let conflicts = validation::validate_path_changes(model);
+    if !conflicts.is_empty() {
+        return conflicts;
+    }
+
+    if model.settings.confirm_operations {
+        let changes = model.files.current.buffer.undo.get_uncommited_changes();
+        let operations = get_path_operations(&model.files.current.path, changes);
+        if !operations.is_empty() {
+            return confirm_path_operations(model, operations);
+        }
+    }
+
+    apply_path_changes(model)
+}
+
+// NOTE: quitting waits for a pending confirmation to persist the changes first
+pub fn quit(model: &mut Model, mode: &QuitMode) -> Vec<Action> {
+    if mode == &QuitMode::Force {
+        return vec![Action::Quit(mode.clone(), None)];
+    }
+
+    if let Some(CommandLinePrompt::ConfirmPathChanges(quit)) = &mut model.commandline.prompt {
+        *quit = Some(mode.clone());
+        return Vec::new();
+    }
+
+    // NOTE: conflicting changes stay uncommited on save, thus quitting would drop them
+    let changes = model.files.current.buffer.undo.get_uncommited_changes();
+    if !changes.is_empty() {
+        return vec![action::emit_error(
+            "current directory has unsaved changes, save with :w or quit with :q!",
+        )];
+    }
+
+    vec![Action::Quit(mode.clone(), None)]
+}
+
+pub fn resolve_path_changes_confirmation(
+    model: &mut Model,
+    quit: Option<QuitMode>,
+    answer: &str,
+) -> Vec<Action> {
+    if answer == "y" {
+        let mut actions = apply_path_changes(model);
+        if let Some(mode) = quit {
+            actions.push(Action::Quit(mode, None));
+        }
+        return actions;
+    }
+
+    // NOTE: changes stay uncommited, normal mode enables fixing them or reverting with u
+    tracing::info!("applying path changes canceled with answer {:?}", answer);
+    model.mode_before = Some(Mode::Normal);
+
+    Vec::new()
+}
+
+fn confirm_path_operations(model: &mut Model, operations: Vec<PathOperation>) -> Vec<Action> {
+    let mut content = vec![PrintContent::Default(format!(
+        "{} operation(s) planned:",
+        operations.len()
+    ))];
+
+    content.extend(operations.iter().map(|operation| {
+        PrintContent::Default(match operation {
+            PathOperation::Add(path) => format!("create {}", path.display()),
+            PathOperation::Delete(path) => format!("delete {}", path.display()),
+            PathOperation::Rename(old, new) => {
+                format!("rename {} -> {}", old.display(), new.display())
+            }
+            PathOperation::Trash(path) => format!("trash {}", path.display()),
+        })
+    }));
+
+    content.push(PrintContent::Information(
+        "apply? [y]es or [n]o".to_string(),
+    ));
+
+    model.commandline.prompt = Some(CommandLinePrompt::ConfirmPathChanges(None));
+    print_in_commandline(model, &content)
+}
+
+fn apply_path_changes(model: &mut Model) -> Vec<Action> {
     let selection = get_current_selected_bufferline(model).map(|line| line.content.clone());
 
     let mut content: Vec<_> = model.files.current.buffer.lines.drain(..).collect();
-    content.retain(|line| !line.content.is_empty());
+    content.retain(|line| {
+        !line.content.is_empty() && !line.signs.iter().any(|sign| sign.id == REMOVED_SIGN_ID)
+    });
 
     update_buffer(
         &mut model.files.current_vp,
@@ -47,31 +159,17 @@ pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
     let mut actions = Vec::new();
     for br in result {
         if let BufferResult::Changes(modifications) = br {
-            let path = &model.files.current.path;
             let mut trashes = Vec::new();
-            for modification in consolidate_modifications(&modifications) {
-                match modification {
-                    BufferChanged::LineAdded(_, name) => {
-                        if !name.is_empty() {
-                            actions.push(Action::Task(Task::AddPath(
-                                path.join(name.to_stripped_string()),
-                            )))
-                        }
-                    }
-                    BufferChanged::LineRemoved(_, name) => {
-                        trashes.push(path.join(name.to_stripped_string()));
-                    }
-                    BufferChanged::Content(_, old_name, new_name) => {
-                        let task = if new_name.is_empty() {
-                            Task::DeletePath(path.join(old_name.to_stripped_string()))
-                        } else {
-                            Task::RenamePath(
-                                path.join(old_name.to_stripped_string()),
-                                path.join(new_name.to_stripped_string()),
-                            )
-                        };
-                        actions.push(Action::Task(task));
+            for operation in get_path_operations(&model.files.current.path, modifications) {
+                match operation {
+                    PathOperation::Add(path) => actions.push(Action::Task(Task::AddPath(path))),
+                    PathOperation::Delete(path) => {
+                        actions.push(Action::Task(Task::DeletePath(path)))
                     }
+                    PathOperation::Rename(old, new) => actions.push(Action::Task(
+                        Task::RenamePath(old, new, ConflictPolicy::Fail),
+                    )),
+                    PathOperation::Trash(path) => trashes.push(path),
                 }
             }
 
@@ -89,5 +187,183 @@ pub fn persist_path_changes(model: &mut Model) -> Vec<Action> {
             }
         }
     }
+
+    let journal_actions = journal::add_group(&mut model.journal, &actions);
+    actions.extend(journal_actions);
+
     actions
 }
+
+#[derive(Debug, PartialEq)]
+enum PathOperation {
+    Add(PathBuf),
+    Delete(PathBuf),
+    Rename(PathBuf, PathBuf),
+    Trash(PathBuf),
+}
+
+fn get_path_operations(path: &Path, changes: Vec<BufferChanged>) -> Vec<PathOperation> {
+    let mut operations = Vec::new();
+    let mut trashes = Vec::new();
+    for modification in consolidate_modifications(&changes) {
+        match modification {
+            BufferChanged::LineAdded(_, name) => {
+                if !name.is_empty() {
+                    operations.push(PathOperation::Add(path.join(name.to_stripped_string())));
+                }
+            }
+            BufferChanged::LineRemoved(_, line) => {
+                trashes.push(PathOperation::Trash(
+                    path.join(line.content.to_stripped_string()),
+                ));
+            }
+            BufferChanged::Content(_, old_name, new_name) => {
+                let old = path.join(old_name.to_stripped_string());
+                operations.push(if new_name.is_empty() {
+                    PathOperation::Delete(old)
+                } else {
+                    PathOperation::Rename(old, path.join(new_name.to_stripped_string()))
+                });
+            }
+        }
+    }
+
+    operations.extend(trashes);
+    operations
+}
+
+mod test {
+    #[test]
+    fn get_path_operations() {
+        use std::path::{Path, PathBuf};
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine};
+
+        use super::PathOperation;
+
+        let changes = vec![
+            BufferChanged::LineAdded(0, Ansi::new("new")),
+            BufferChanged::LineRemoved(1, BufferLine::from("old")),
+            BufferChanged::Content(2, Ansi::new("a"), Ansi::new("b")),
+            BufferChanged::Content(3, Ansi::new("c"), Ansi::new("")),
+        ];
+
+        let operations = super::get_path_operations(Path::new("/root"), changes);
+
+        assert_eq!(
+            vec![
+                PathOperation::Add(PathBuf::from("/root/new")),
+                PathOperation::Rename(PathBuf::from("/root/a"), PathBuf::from("/root/b")),
+                PathOperation::Delete(PathBuf::from("/root/c")),
+                PathOperation::Trash(PathBuf::from("/root/old")),
+            ],
+            operations
+        );
+    }
+
+    #[test]
+    fn wq_waits_for_path_changes_confirmation() {
+        use std::fs;
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+        use yeet_keymap::message::{KeySequence, QuitMode};
+
+        use crate::{
+            action::Action,
+            event::{Envelope, MessageSource},
+            model::{CommandLinePrompt, Model},
+        };
+
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("a"), "").unwrap();
+
+        let mut model = Model::default();
+        model.settings.confirm_operations = true;
+        model.commandline.viewport.height = 1;
+        model.files.current_vp.height = 10;
+        model.files.current.path = temp.path().to_path_buf();
+        model.files.current.buffer.lines = vec![BufferLine::from("b")];
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::Content(0, Ansi::new("a"), Ansi::new("b"))],
+        );
+
+        let messages = match super::super::command::execute("wq", &mut model).pop() {
+            Some(Action::EmitMessages(messages)) => messages,
+            _ => panic!("wq must emit messages"),
+        };
+
+        let envelope = Envelope {
+            messages,
+            sequence: KeySequence::None,
+            source: MessageSource::User,
+        };
+
+        let actions = super::super::update_model(&mut model, envelope);
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, Action::Quit(_, _))));
+
+        let quit = match model.commandline.prompt.take() {
+            Some(CommandLinePrompt::ConfirmPathChanges(quit)) => quit,
+            _ => panic!("wq must ask for confirmation"),
+        };
+        assert_eq!(Some(QuitMode::FailOnRunningTasks), quit);
+
+        let actions = super::resolve_path_changes_confirmation(&mut model, quit, "y");
+        assert!(actions
+            .iter()
+            .any(|action| matches!(action, Action::Quit(QuitMode::FailOnRunningTasks, _))));
+    }
+
+    #[test]
+    fn wq_refuses_to_quit_on_conflicts() {
+        use std::fs;
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged, BufferLine, Mode};
+        use yeet_keymap::message::KeySequence;
+
+        use crate::{
+            action::Action,
+            event::{Envelope, MessageSource},
+            model::{Model, ERROR_SIGN_ID},
+        };
+
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("a"), "").unwrap();
+        fs::write(temp.path().join("b"), "").unwrap();
+
+        let mut model = Model::default();
+        model.commandline.viewport.height = 1;
+        model.files.current_vp.height = 10;
+        model.files.current.path = temp.path().to_path_buf();
+        model.files.current.buffer.lines = vec![BufferLine::from("b"), BufferLine::from("b")];
+        model.files.current.buffer.undo.add(
+            &Mode::Normal,
+            vec![BufferChanged::Content(0, Ansi::new("a"), Ansi::new("b"))],
+        );
+
+        let messages = match super::super::command::execute("wq", &mut model).pop() {
+            Some(Action::EmitMessages(messages)) => messages,
+            _ => panic!("wq must emit messages"),
+        };
+
+        let envelope = Envelope {
+            messages,
+            sequence: KeySequence::None,
+            source: MessageSource::User,
+        };
+
+        let actions = super::super::update_model(&mut model, envelope);
+        assert!(!actions
+            .iter()
+            .any(|action| matches!(action, Action::Quit(_, _))));
+
+        let buffer = &model.files.current.buffer;
+        assert!(!buffer.undo.get_uncommited_changes().is_empty());
+        assert!(buffer.lines[0]
+            .signs
+            .iter()
+            .any(|sign| sign.id == ERROR_SIGN_ID));
+    }
+}
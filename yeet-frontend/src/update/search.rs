@@ -1,10 +1,16 @@
-use yeet_buffer::model::Buffer;
+use regex::{Regex, RegexBuilder};
+use yeet_buffer::{
+    message::{BufferMessage, CursorDirection, Search},
+    model::{Buffer, SearchDirection},
+};
 
 use crate::{
     action::Action,
     model::{BufferType, Model},
 };
 
+use super::{selection, update_focused};
+
 pub fn search_in_buffers(model: &mut Model, search: Option<String>) {
     let search = match search {
         Some(it) => it,
@@ -23,12 +29,69 @@ pub fn search_in_buffers(model: &mut Model, search: Option<String>) {
     };
 
     if let BufferType::Text(path, buffer) = &mut model.files.preview {
-        if path.is_dir() {
+        if path.is_dir() || model.files.preview_focused {
             set_search_char_positions(buffer, search.as_str());
         }
     };
 }
 
+pub fn search_incrementally(
+    model: &mut Model,
+    direction: &SearchDirection,
+    search: Option<String>,
+) -> Vec<Action> {
+    // NOTE: every keystroke searches from the position the search got started on
+    let (focused_cursor, focused_vp) = if model.files.preview_focused {
+        (&mut model.files.preview_cursor, &mut model.files.preview_vp)
+    } else {
+        (&mut model.files.current_cursor, &mut model.files.current_vp)
+    };
+
+    match &model.files.search_origin {
+        Some((cursor, vertical_index)) => {
+            *focused_cursor = cursor.clone();
+            focused_vp.vertical_index = *vertical_index;
+        }
+        None => {
+            model.files.search_origin = Some((focused_cursor.clone(), focused_vp.vertical_index));
+        }
+    }
+
+    let is_search = search.is_some();
+    search_in_buffers(model, search);
+
+    if is_search {
+        let search = match direction {
+            SearchDirection::Down => Search::Next,
+            SearchDirection::Up => Search::Previous,
+        };
+
+        update_focused(
+            model,
+            &BufferMessage::MoveCursor(1, CursorDirection::Search(search)),
+        );
+    }
+
+    selection::load_preview_on_selection_change(model)
+}
+
+pub fn reset_incremental_search(model: &mut Model) -> Vec<Action> {
+    let (cursor, vertical_index) = match model.files.search_origin.take() {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    if model.files.preview_focused {
+        model.files.preview_cursor = cursor;
+        model.files.preview_vp.vertical_index = vertical_index;
+    } else {
+        model.files.current_cursor = cursor;
+        model.files.current_vp.vertical_index = vertical_index;
+    }
+
+    selection::load_preview_on_selection_change(model)
+}
+
 pub fn clear_search(model: &mut Model) -> Vec<Action> {
     for line in &mut model.files.current.buffer.lines {
         line.search_char_position = None;
@@ -47,23 +110,139 @@ pub fn clear_search(model: &mut Model) -> Vec<Action> {
 }
 
 fn set_search_char_positions(buffer: &mut Buffer, search: &str) {
-    let smart_case = search.chars().all(|c| c.is_ascii_lowercase());
-    let search_length = search.chars().count();
+    let regex = match get_search_regex(search) {
+        Some(it) => it,
+        None => return,
+    };
 
     for line in &mut buffer.lines {
         line.search_char_position = None;
 
-        let mut content = line.content.to_stripped_string();
-        let lower = content.to_lowercase();
-        if smart_case {
-            content = lower;
-        };
+        let content = line.content.to_stripped_string();
+        let positions: Vec<_> = regex
+            .find_iter(&content)
+            .filter(|m| !m.is_empty())
+            .map(|m| {
+                let start = content[..m.start()].chars().count();
+                (start, m.as_str().chars().count())
+            })
+            .collect();
 
-        let start = match content.find(search) {
-            Some(it) => content[..it].chars().count(),
-            None => continue,
+        if !positions.is_empty() {
+            line.search_char_position = Some(positions);
+        }
+    }
+}
+
+pub fn get_search_regex(search: &str) -> Option<Regex> {
+    let (search, case_insensitive) = if search.contains("\\C") {
+        (search.replace("\\C", "").replace("\\c", ""), false)
+    } else if search.contains("\\c") {
+        (search.replace("\\c", ""), true)
+    } else {
+        // NOTE: smart case like vim, upper case chars make the search case sensitive
+        let smart_case = !search.chars().any(char::is_uppercase);
+        (search.to_owned(), smart_case)
+    };
+
+    if search.is_empty() {
+        return None;
+    }
+
+    let regex = RegexBuilder::new(&search)
+        .case_insensitive(case_insensitive)
+        .build();
+
+    match regex {
+        Ok(it) => Some(it),
+        Err(err) => {
+            tracing::debug!(
+                "invalid search regex {:?}, searching literally: {}",
+                search,
+                err
+            );
+
+            RegexBuilder::new(&regex::escape(&search))
+                .case_insensitive(case_insensitive)
+                .build()
+                .ok()
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn set_search_char_positions_multiple_matches() {
+        use yeet_buffer::model::{Buffer, BufferLine};
+
+        let mut buffer = Buffer {
+            lines: vec![BufferLine::from("fööbar foobar"), BufferLine::from("bar")],
+            ..Default::default()
         };
 
-        line.search_char_position = Some(vec![(start, search_length)]);
+        super::set_search_char_positions(&mut buffer, "f.+?bar");
+
+        assert_eq!(
+            Some(vec![(0, 6), (7, 6)]),
+            buffer.lines[0].search_char_position
+        );
+        assert_eq!(None, buffer.lines[1].search_char_position);
+    }
+
+    #[test]
+    fn get_search_regex_case_flags() {
+        let regex = super::get_search_regex("foo").unwrap();
+        assert!(regex.is_match("FOO"));
+
+        let regex = super::get_search_regex("Foo").unwrap();
+        assert!(!regex.is_match("foo"));
+
+        let regex = super::get_search_regex("Foo\\c").unwrap();
+        assert!(regex.is_match("foo"));
+
+        let regex = super::get_search_regex("foo\\C").unwrap();
+        assert!(!regex.is_match("FOO"));
+
+        let regex = super::get_search_regex("foo(").unwrap();
+        assert!(regex.is_match("FOO("));
+    }
+
+    #[test]
+    fn search_incrementally_and_reset_to_origin() {
+        use ratatui::layout::Rect;
+        use yeet_buffer::model::{ansi::Ansi, BufferLine, SearchDirection};
+
+        use crate::model::Model;
+
+        let mut model = Model::default();
+        model.layout.current = Rect::new(0, 0, 20, 10);
+        model.files.current.buffer.lines = ["a", "bar", "baz"]
+            .iter()
+            .map(|content| BufferLine {
+                content: Ansi::new(content),
+                ..Default::default()
+            })
+            .collect();
+
+        let down = SearchDirection::Down;
+        super::search_incrementally(&mut model, &down, Some("b".to_owned()));
+        super::search_incrementally(&mut model, &down, Some("baz".to_owned()));
+
+        let vertical_index = model
+            .files
+            .current_cursor
+            .as_ref()
+            .map(|c| c.vertical_index);
+        assert_eq!(Some(2), vertical_index);
+
+        super::reset_incremental_search(&mut model);
+
+        let vertical_index = model
+            .files
+            .current_cursor
+            .as_ref()
+            .map(|c| c.vertical_index);
+        assert_eq!(Some(0), vertical_index);
+        assert!(model.files.search_origin.is_none());
     }
 }
@@ -2,7 +2,31 @@ use std::path::PathBuf;
 
 use yeet_buffer::model::BufferLine;
 
-use crate::{action::Action, event::Message, model::Model};
+use crate::{
+    action::Action,
+    event::Message,
+    model::{Model, WindowType},
+};
+
+use super::history::get_selection_from_history;
+
+pub fn load_preview_on_selection_change(model: &Model) -> Vec<Action> {
+    load_preview_on_change(model, get_current_selected_path(model))
+}
+
+pub fn load_preview_on_change(model: &Model, path: Option<PathBuf>) -> Vec<Action> {
+    if model.files.preview.resolve_path() == path.as_deref() {
+        return Vec::new();
+    }
+
+    match path {
+        Some(path) => {
+            let selection = get_selection_from_history(&model.history, &path).map(|s| s.to_owned());
+            vec![Action::Load(WindowType::Preview, path, selection)]
+        }
+        None => Vec::new(),
+    }
+}
 
 pub fn get_current_selected_path(model: &Model) -> Option<PathBuf> {
     let buffer = &model.files.current.buffer;
@@ -29,6 +53,38 @@ pub fn get_current_selected_path(model: &Model) -> Option<PathBuf> {
     }
 }
 
+pub fn get_visual_selected_paths(model: &Model) -> Vec<PathBuf> {
+    let range = model
+        .files
+        .current_cursor
+        .as_ref()
+        .and_then(|cursor| cursor.get_visual_range());
+
+    let (start, end) = match range {
+        Some(it) => it,
+        None => return Vec::new(),
+    };
+
+    model
+        .files
+        .current
+        .buffer
+        .lines
+        .iter()
+        .skip(start)
+        .take(end - start + 1)
+        .filter(|line| !line.content.is_empty())
+        .map(|line| {
+            model
+                .files
+                .current
+                .path
+                .join(line.content.to_stripped_string())
+        })
+        .filter(|path| path.exists())
+        .collect()
+}
+
 pub fn get_current_selected_bufferline(model: &mut Model) -> Option<&mut BufferLine> {
     let buffer = &mut model.files.current.buffer;
     if buffer.lines.is_empty() {
@@ -2,9 +2,10 @@ use std::path::Path;
 use yeet_buffer::model::{BufferLine, Sign, SignIdentifier};
 
 use crate::model::{
+    git::{Git, GitStatus, GIT_SIGN_ID},
     mark::{Marks, MARK_SIGN_ID},
     qfix::{QuickFix, QFIX_SIGN_ID},
-    Model,
+    FileOperations, Model, ERROR_SIGN_ID, FAILED_SIGN_ID, PENDING_SIGN_ID, REMOVED_SIGN_ID,
 };
 
 pub fn set_sign_if_qfix(qfix: &QuickFix, bl: &mut BufferLine, path: &Path) {
@@ -16,6 +17,34 @@ pub fn set_sign_if_qfix(qfix: &QuickFix, bl: &mut BufferLine, path: &Path) {
     set(bl, QFIX_SIGN_ID);
 }
 
+pub fn set_sign_if_operation(operations: &FileOperations, bl: &mut BufferLine, path: &Path) {
+    if operations.failed.contains_key(path) {
+        set(bl, FAILED_SIGN_ID);
+    }
+
+    let is_pending = operations
+        .pending
+        .values()
+        .any(|paths| paths.iter().any(|p| p == path));
+
+    if is_pending {
+        set(bl, PENDING_SIGN_ID);
+    }
+}
+
+pub fn set_sign_if_git(git: &Git, bl: &mut BufferLine, path: &Path) {
+    let status = match git.get_status(path) {
+        Some(it) => it,
+        None => return,
+    };
+
+    if bl.signs.iter().any(|s| s.id == GIT_SIGN_ID) {
+        return;
+    }
+
+    bl.signs.push(generate_git_sign(status));
+}
+
 pub fn set_sign_if_marked(marks: &Marks, bl: &mut BufferLine, path: &Path) {
     let is_marked = marks.entries.values().any(|p| p == path);
     if !is_marked {
@@ -84,10 +113,51 @@ fn generate_sign(sign_id: SignIdentifier) -> Option<Sign> {
             style: "\x1b[1;96m".to_string(),
             priority: 0,
         }),
+        ERROR_SIGN_ID => Some(Sign {
+            id: ERROR_SIGN_ID,
+            content: 'E',
+            style: "\x1b[1;31m".to_string(),
+            priority: 2,
+        }),
+        FAILED_SIGN_ID => Some(Sign {
+            id: FAILED_SIGN_ID,
+            content: 'F',
+            style: "\x1b[1;31m".to_string(),
+            priority: 2,
+        }),
+        PENDING_SIGN_ID => Some(Sign {
+            id: PENDING_SIGN_ID,
+            content: '*',
+            style: "\x1b[90m".to_string(),
+            priority: 1,
+        }),
+        REMOVED_SIGN_ID => Some(Sign {
+            id: REMOVED_SIGN_ID,
+            content: 'D',
+            style: "\x1b[1;91m".to_string(),
+            priority: 1,
+        }),
         _ => None,
     }
 }
 
+fn generate_git_sign(status: &GitStatus) -> Sign {
+    let (content, style) = match status {
+        GitStatus::Conflicted => ('U', "\x1b[1;91m"),
+        GitStatus::Ignored => ('!', "\x1b[90m"),
+        GitStatus::Modified => ('~', "\x1b[1;93m"),
+        GitStatus::Staged => ('+', "\x1b[1;92m"),
+        GitStatus::Untracked => ('?', "\x1b[1;94m"),
+    };
+
+    Sign {
+        id: GIT_SIGN_ID,
+        content,
+        style: style.to_string(),
+        priority: 0,
+    }
+}
+
 pub fn unset_sign_on_all_buffers(model: &mut Model, sign_id: SignIdentifier) {
     model
         .files
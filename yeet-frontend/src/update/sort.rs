@@ -0,0 +1,339 @@
+use std::{
+    cmp::Ordering,
+    fs::{self, Metadata},
+    path::Path,
+    time::SystemTime,
+};
+
+use yeet_buffer::{message::BufferMessage, model::BufferLine, update::update_buffer};
+
+use crate::{
+    action::{self, Action},
+    model::Model,
+    settings::{SortMode, SortSettings},
+};
+
+use super::save::get_unsaved_changes_error;
+
+struct SortKey {
+    line: BufferLine,
+    name: String,
+    is_dir: bool,
+    size: u64,
+    time: Option<SystemTime>,
+}
+
+pub fn set_sort(model: &mut Model, args: &str, reverse: bool) -> Vec<Action> {
+    let mut settings = SortSettings {
+        reverse,
+        ..Default::default()
+    };
+
+    let mut mode = None;
+    for arg in args.split_whitespace() {
+        match arg {
+            "ctime" => mode = Some(SortMode::Ctime),
+            "extension" => mode = Some(SortMode::Extension),
+            "mtime" => mode = Some(SortMode::Mtime),
+            "name" => mode = Some(SortMode::Name),
+            "natural" => mode = Some(SortMode::Natural),
+            "size" => mode = Some(SortMode::Size),
+            "dirsfirst" => settings.directories_first = true,
+            "reverse" => settings.reverse = true,
+            _ => {
+                return vec![action::emit_error(&format!(
+                    "sort option '{}' is not valid",
+                    arg
+                ))]
+            }
+        }
+    }
+
+    settings.mode = match mode {
+        Some(it) => it,
+        None => {
+            return vec![action::emit_error(
+                "sort needs a mode: name, natural, size, mtime, ctime or extension",
+            )]
+        }
+    };
+
+    if model.files.bulk_rename.is_some() {
+        return vec![action::emit_error(
+            "sort is not available while bulk renaming",
+        )];
+    }
+
+    if let Some(error) = get_unsaved_changes_error(&model.files) {
+        return vec![error];
+    }
+
+    model.settings.sort = settings;
+
+    let sort = &model.settings.sort;
+    let mode = &model.mode;
+    for (path, viewport, cursor, buffer) in model.files.get_mut_directories() {
+        let selection = cursor
+            .as_ref()
+            .and_then(|cursor| buffer.lines.get(cursor.vertical_index))
+            .map(|line| line.content.to_stripped_string());
+
+        let lines = get_sorted_lines(sort, path, buffer.lines.drain(..).collect());
+        update_buffer(
+            viewport,
+            cursor,
+            mode,
+            buffer,
+            &BufferMessage::SetContent(lines),
+        );
+
+        if let Some(selection) = selection {
+            update_buffer(
+                viewport,
+                cursor,
+                mode,
+                buffer,
+                &BufferMessage::SetCursorToLineContent(selection),
+            );
+        }
+    }
+
+    Vec::new()
+}
+
+pub fn get_sorted_lines(
+    settings: &SortSettings,
+    path: &Path,
+    lines: Vec<BufferLine>,
+) -> Vec<BufferLine> {
+    let with_metadata = settings.directories_first
+        || matches!(
+            settings.mode,
+            SortMode::Ctime | SortMode::Mtime | SortMode::Size
+        );
+
+    let mut keys: Vec<_> = lines
+        .into_iter()
+        .map(|line| {
+            let name = line.content.to_stripped_string();
+            let metadata = if with_metadata {
+                fs::metadata(path.join(&name)).ok()
+            } else {
+                None
+            };
+
+            SortKey {
+                is_dir: metadata.as_ref().is_some_and(|m| m.is_dir()),
+                size: metadata.as_ref().map_or(0, |m| m.len()),
+                time: metadata.as_ref().and_then(|m| get_time(&settings.mode, m)),
+                name,
+                line,
+            }
+        })
+        .collect();
+
+    keys.sort_by(|a, b| {
+        let directories = if settings.directories_first {
+            b.is_dir.cmp(&a.is_dir)
+        } else {
+            Ordering::Equal
+        };
+
+        let ordering = match settings.mode {
+            SortMode::Ctime | SortMode::Mtime => a.time.cmp(&b.time),
+            SortMode::Extension => get_extension(&a.name).cmp(&get_extension(&b.name)),
+            SortMode::Name => Ordering::Equal,
+            SortMode::Natural => compare_natural(&a.name, &b.name),
+            SortMode::Size => a.size.cmp(&b.size),
+        }
+        .then_with(|| compare_name(&a.name, &b.name));
+
+        let ordering = if settings.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+
+        directories.then(ordering)
+    });
+
+    keys.into_iter().map(|key| key.line).collect()
+}
+
+fn get_time(mode: &SortMode, metadata: &Metadata) -> Option<SystemTime> {
+    match mode {
+        SortMode::Ctime => get_ctime(metadata),
+        SortMode::Mtime => metadata.modified().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(unix)]
+fn get_ctime(metadata: &Metadata) -> Option<SystemTime> {
+    use std::{os::unix::fs::MetadataExt, time::Duration};
+
+    let seconds = u64::try_from(metadata.ctime()).ok()?;
+    let nanos = u32::try_from(metadata.ctime_nsec()).ok()?;
+
+    SystemTime::UNIX_EPOCH.checked_add(Duration::new(seconds, nanos))
+}
+
+#[cfg(not(unix))]
+fn get_ctime(metadata: &Metadata) -> Option<SystemTime> {
+    metadata.created().ok()
+}
+
+fn get_extension(name: &str) -> String {
+    Path::new(name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_uppercase())
+        .unwrap_or_default()
+}
+
+fn compare_name(a: &str, b: &str) -> Ordering {
+    a.to_ascii_uppercase().cmp(&b.to_ascii_uppercase())
+}
+
+// NOTE: compares digit sequences by value, thus file2 is ordered before file10
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_number = take_number(&mut a_chars);
+                let b_number = take_number(&mut b_chars);
+
+                let a_trimmed = a_number.trim_start_matches('0');
+                let b_trimmed = b_number.trim_start_matches('0');
+                let ordering = a_trimmed
+                    .len()
+                    .cmp(&b_trimmed.len())
+                    .then_with(|| a_trimmed.cmp(b_trimmed));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char
+                    .to_ascii_uppercase()
+                    .cmp(&b_char.to_ascii_uppercase());
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut number = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        number.push(c);
+    }
+    number
+}
+
+mod test {
+    #[test]
+    fn get_sorted_lines_natural() {
+        use std::path::Path;
+
+        use yeet_buffer::model::BufferLine;
+
+        use crate::settings::{SortMode, SortSettings};
+
+        let lines = vec![
+            BufferLine::from("file10"),
+            BufferLine::from("File2"),
+            BufferLine::from("file1"),
+        ];
+
+        let settings = SortSettings {
+            mode: SortMode::Natural,
+            ..Default::default()
+        };
+        let result = super::get_sorted_lines(&settings, Path::new("/yeet/not/existing"), lines);
+        let result: Vec<_> = result
+            .iter()
+            .map(|line| line.content.to_stripped_string())
+            .collect();
+
+        assert_eq!(vec!["file1", "File2", "file10"], result);
+    }
+
+    #[test]
+    fn get_sorted_lines_extension_reverse() {
+        use std::path::Path;
+
+        use yeet_buffer::model::BufferLine;
+
+        use crate::settings::{SortMode, SortSettings};
+
+        let lines = vec![
+            BufferLine::from("b.rs"),
+            BufferLine::from("a.toml"),
+            BufferLine::from("a.rs"),
+        ];
+
+        let settings = SortSettings {
+            mode: SortMode::Extension,
+            reverse: true,
+            ..Default::default()
+        };
+        let result = super::get_sorted_lines(&settings, Path::new("/yeet/not/existing"), lines);
+        let result: Vec<_> = result
+            .iter()
+            .map(|line| line.content.to_stripped_string())
+            .collect();
+
+        assert_eq!(vec!["a.toml", "b.rs", "a.rs"], result);
+    }
+
+    #[test]
+    fn get_sorted_lines_directories_first() {
+        use std::fs;
+
+        use yeet_buffer::model::BufferLine;
+
+        use crate::settings::{SortMode, SortSettings};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("b")).unwrap();
+        fs::write(root.join("a"), "").unwrap();
+
+        let settings = SortSettings {
+            mode: SortMode::Name,
+            directories_first: true,
+            ..Default::default()
+        };
+        let lines = vec![BufferLine::from("a"), BufferLine::from("b")];
+        let result = super::get_sorted_lines(&settings, root, lines);
+
+        let result: Vec<_> = result
+            .iter()
+            .map(|line| line.content.to_stripped_string())
+            .collect();
+
+        assert_eq!(vec!["b", "a"], result);
+    }
+
+    #[test]
+    fn compare_natural() {
+        use std::cmp::Ordering;
+
+        assert_eq!(Ordering::Less, super::compare_natural("a2", "a10"));
+        assert_eq!(Ordering::Less, super::compare_natural("a02b", "a2c"));
+        assert_eq!(Ordering::Greater, super::compare_natural("b", "A"));
+        assert_eq!(Ordering::Equal, super::compare_natural("a01", "a1"));
+    }
+}
@@ -1,12 +1,68 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, path::PathBuf};
 
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     action::Action,
-    model::{CurrentTask, Model},
+    event::Message,
+    model::{CurrentTask, Model, FAILED_SIGN_ID, PENDING_SIGN_ID},
+    task::Task,
 };
 
+use super::sign::{set_sign_for_path, unset_sign_for_path};
+
+pub fn add_pending_operations(model: &mut Model, actions: &[Action]) {
+    let operations: Vec<_> = actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Task(task) => Some((task.to_string(), get_affected_paths(task))),
+            _ => None,
+        })
+        .filter(|(_, paths)| !paths.is_empty())
+        .collect();
+
+    if operations.is_empty() {
+        return;
+    }
+
+    // NOTE: failures are kept until the next file operations start
+    let failed: Vec<_> = model
+        .operations
+        .failed
+        .drain()
+        .map(|(path, _)| path)
+        .collect();
+    for path in failed {
+        unset_sign_for_path(model, &path, FAILED_SIGN_ID);
+    }
+
+    for (identifier, paths) in operations {
+        for path in paths.iter() {
+            set_sign_for_path(model, path, PENDING_SIGN_ID);
+        }
+
+        model.operations.pending.insert(identifier, paths);
+    }
+}
+
+fn get_affected_paths(task: &Task) -> Vec<PathBuf> {
+    match task {
+        Task::AddPath(path) | Task::DeletePath(path) => vec![path.clone()],
+        Task::CopyPath(_, target, _) => vec![target.clone()],
+        Task::RenamePath(old, new, _) => vec![old.clone(), new.clone()],
+        Task::RenamePaths(renames) => renames
+            .iter()
+            .flat_map(|(old, new)| [old.clone(), new.clone()])
+            .collect(),
+        Task::RestorePath(entry, path, _) => match entry.target.file_name() {
+            Some(file_name) => vec![path.join(file_name)],
+            None => Vec::new(),
+        },
+        Task::TrashPath(entry) => vec![entry.target.clone()],
+        _ => Vec::new(),
+    }
+}
+
 pub fn add(model: &mut Model, identifier: String, cancellation: CancellationToken) -> Vec<Action> {
     let id = next_id(model);
 
@@ -16,6 +72,7 @@ pub fn add(model: &mut Model, identifier: String, cancellation: CancellationToke
             token: cancellation,
             id,
             external_id: identifier,
+            progress: None,
         },
     ) {
         replaced_task.token.cancel();
@@ -46,9 +103,104 @@ fn next_id(model: &mut Model) -> u16 {
     next_id
 }
 
+pub fn update_progress(
+    model: &mut Model,
+    identifier: String,
+    done: usize,
+    total: usize,
+) -> Vec<Action> {
+    if let Some(task) = model.current_tasks.get_mut(&identifier) {
+        task.progress = Some((done, total));
+    }
+    Vec::new()
+}
+
+pub fn fail(model: &mut Model, identifier: String, reason: String) -> Vec<Action> {
+    let paths = match model.operations.pending.get(&identifier) {
+        Some(it) => it.clone(),
+        None => return Vec::new(),
+    };
+
+    let mut messages = Vec::new();
+    for path in paths {
+        set_sign_for_path(model, &path, FAILED_SIGN_ID);
+        messages.push(Message::Error(format!("{}: {}", path.display(), reason)));
+        model.operations.failed.insert(path, reason.clone());
+    }
+
+    vec![Action::EmitMessages(messages)]
+}
+
 pub fn remove(model: &mut Model, identifier: String) -> Vec<Action> {
     if let Some(task) = model.current_tasks.remove(&identifier) {
         task.token.cancel();
     }
+
+    if let Some(paths) = model.operations.pending.remove(&identifier) {
+        for path in paths {
+            let is_pending = model
+                .operations
+                .pending
+                .values()
+                .any(|pending| pending.contains(&path));
+
+            if !is_pending {
+                unset_sign_for_path(model, &path, PENDING_SIGN_ID);
+            }
+        }
+    }
+
     Vec::new()
 }
+
+mod test {
+    #[test]
+    fn fail_keeps_error_after_task_ended() {
+        use std::path::PathBuf;
+
+        use crate::{action::Action, model::Model, task::Task};
+
+        let mut model = Model::default();
+        let task = Task::AddPath(PathBuf::from("/tmp/yeet/file"));
+        let identifier = task.to_string();
+
+        super::add_pending_operations(&mut model, &[Action::Task(task)]);
+        assert_eq!(
+            model.operations.pending.get(&identifier),
+            Some(&vec![PathBuf::from("/tmp/yeet/file")])
+        );
+
+        let actions = super::fail(&mut model, identifier.clone(), "denied".to_owned());
+        assert_eq!(actions.len(), 1);
+
+        super::remove(&mut model, identifier);
+        assert!(model.operations.pending.is_empty());
+        assert_eq!(
+            model
+                .operations
+                .failed
+                .get(&PathBuf::from("/tmp/yeet/file")),
+            Some(&"denied".to_owned())
+        );
+    }
+
+    #[test]
+    fn add_pending_operations_clears_previous_failures() {
+        use std::path::PathBuf;
+
+        use crate::{action::Action, model::Model, task::Task};
+
+        let mut model = Model::default();
+        model
+            .operations
+            .failed
+            .insert(PathBuf::from("/tmp/yeet/old"), "denied".to_owned());
+
+        super::add_pending_operations(&mut model, &[Action::ModeChanged]);
+        assert_eq!(1, model.operations.failed.len());
+
+        let task = Task::AddPath(PathBuf::from("/tmp/yeet/new"));
+        super::add_pending_operations(&mut model, &[Action::Task(task)]);
+        assert!(model.operations.failed.is_empty());
+    }
+}
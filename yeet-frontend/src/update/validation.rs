@@ -0,0 +1,233 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path},
+};
+
+use yeet_buffer::{
+    message::BufferMessage,
+    model::{
+        undo::{consolidate_modifications, BufferChanged},
+        Mode,
+    },
+};
+use yeet_keymap::message::{KeymapMessage, PrintContent};
+
+use crate::{
+    action::Action,
+    event::Message,
+    model::{Model, ERROR_SIGN_ID, REMOVED_SIGN_ID},
+};
+
+use super::sign;
+
+// NOTE: returns the actions to report conflicts, an empty result allows persisting the changes
+pub fn validate_path_changes(model: &mut Model) -> Vec<Action> {
+    let buffer = &mut model.files.current.buffer;
+    let lines: Vec<_> = buffer
+        .lines
+        .iter()
+        .map(|line| line.content.to_stripped_string())
+        .collect();
+
+    // NOTE: lines removed externally are skipped on save, thus they can not conflict
+    let removed: HashSet<_> = buffer
+        .lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.signs.iter().any(|sign| sign.id == REMOVED_SIGN_ID))
+        .map(|(index, _)| index)
+        .collect();
+
+    let changes = buffer.undo.get_uncommited_changes();
+    let conflicts = if changes.is_empty() {
+        Vec::new()
+    } else {
+        get_conflicts(&model.files.current.path, &lines, &removed, changes)
+    };
+
+    for (index, line) in buffer.lines.iter_mut().enumerate() {
+        if conflicts.iter().any(|(conflict, _)| conflict == &index) {
+            sign::set(line, ERROR_SIGN_ID);
+        } else {
+            sign::unset(line, ERROR_SIGN_ID);
+        }
+    }
+
+    if conflicts.is_empty() {
+        return Vec::new();
+    }
+
+    let mut content = vec![PrintContent::Error(format!(
+        "{} conflict(s) found, nothing was written:",
+        conflicts.len()
+    ))];
+
+    content.extend(conflicts.into_iter().map(|(index, reason)| {
+        PrintContent::Error(format!("line {}: {}: {}", index + 1, lines[index], reason))
+    }));
+
+    // NOTE: switching to normal first enables fixing the lines after reading the conflicts
+    vec![Action::EmitMessages(vec![
+        Message::Keymap(KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            model.mode.clone(),
+            Mode::Normal,
+        ))),
+        Message::Keymap(KeymapMessage::Print(content)),
+    ])]
+}
+
+fn get_conflicts(
+    path: &Path,
+    lines: &[String],
+    removed: &HashSet<usize>,
+    changes: Vec<BufferChanged>,
+) -> Vec<(usize, String)> {
+    let position = |target: &str| {
+        lines
+            .iter()
+            .enumerate()
+            .position(|(index, line)| line == target && !removed.contains(&index))
+    };
+
+    let mut conflicts = Vec::new();
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_empty() || removed.contains(&index) {
+            continue;
+        }
+
+        if line.contains('\0') {
+            conflicts.push((index, "name contains nul bytes".to_owned()));
+            continue;
+        }
+
+        let is_outside = Path::new(line).components().any(|component| {
+            matches!(
+                component,
+                Component::ParentDir | Component::Prefix(_) | Component::RootDir
+            )
+        });
+
+        if is_outside {
+            conflicts.push((index, "name points outside of the directory".to_owned()));
+            continue;
+        }
+
+        let name = line.trim_end_matches('/');
+        match names.get(name) {
+            Some(first) => {
+                conflicts.push((index, format!("name is already used in line {}", first + 1)))
+            }
+            None => {
+                names.insert(name, index);
+            }
+        }
+    }
+
+    // NOTE: existing paths are only valid targets, if the current save moves them away
+    let mut released = HashSet::new();
+    let mut targets = Vec::new();
+    let mut missing = Vec::new();
+    for change in consolidate_modifications(&changes) {
+        match change {
+            BufferChanged::Content(_, old, new) => {
+                let old = old.to_stripped_string();
+                if !new.is_empty() {
+                    if path.join(&old).symlink_metadata().is_err() {
+                        missing.push(new.to_stripped_string());
+                    }
+                    targets.push(new.to_stripped_string());
+                }
+                released.insert(old);
+            }
+            BufferChanged::LineAdded(_, new) => {
+                if !new.is_empty() {
+                    targets.push(new.to_stripped_string());
+                }
+            }
+            BufferChanged::LineRemoved(_, old) => {
+                released.insert(old.content.to_stripped_string());
+            }
+        }
+    }
+
+    for target in missing {
+        let index = match position(&target) {
+            Some(it) => it,
+            None => continue,
+        };
+
+        if !conflicts.iter().any(|(conflict, _)| conflict == &index) {
+            conflicts.push((index, "renamed path was removed externally".to_owned()));
+        }
+    }
+
+    for target in targets {
+        if released.contains(&target) || path.join(&target).symlink_metadata().is_err() {
+            continue;
+        }
+
+        let index = match position(&target) {
+            Some(it) => it,
+            None => continue,
+        };
+
+        if !conflicts.iter().any(|(conflict, _)| conflict == &index) {
+            conflicts.push((index, "path already exists".to_owned()));
+        }
+    }
+
+    conflicts.sort_by_key(|(index, _)| *index);
+    conflicts
+}
+
+mod test {
+    #[test]
+    fn get_conflicts() {
+        use std::{collections::HashSet, fs};
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::write(root.join("existing"), "").unwrap();
+        fs::write(root.join("moved"), "").unwrap();
+
+        let lines: Vec<_> = ["a", "a/", "../b", "c\0", "", "existing", "moved", "renamed"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let changes = vec![
+            BufferChanged::Content(5, Ansi::new("old"), Ansi::new("existing")),
+            BufferChanged::Content(7, Ansi::new("moved"), Ansi::new("renamed")),
+            BufferChanged::LineAdded(6, Ansi::new("moved")),
+        ];
+
+        let conflicts = super::get_conflicts(root, &lines, &HashSet::new(), changes);
+
+        let indices: Vec<_> = conflicts.iter().map(|(index, _)| *index).collect();
+        assert_eq!(vec![1, 2, 3, 5], indices);
+    }
+
+    #[test]
+    fn get_conflicts_skips_removed_lines() {
+        use std::{collections::HashSet, fs};
+
+        use yeet_buffer::model::{ansi::Ansi, undo::BufferChanged};
+
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join("c"), "").unwrap();
+
+        let lines: Vec<_> = ["a", "b", "a"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let changes = vec![BufferChanged::Content(2, Ansi::new("c"), Ansi::new("a"))];
+        let removed = HashSet::from([0]);
+
+        let conflicts = super::get_conflicts(temp.path(), &lines, &removed, changes);
+        assert!(conflicts.is_empty());
+    }
+}
@@ -13,7 +13,10 @@ use super::{history, selection};
 
 pub fn move_viewport(model: &mut Model, direction: &ViewPortDirection) -> Vec<Action> {
     let msg = BufferMessage::MoveViewPort(direction.clone());
-    super::update_current(model, &msg);
+    super::update_focused(model, &msg);
+    if model.files.preview_focused {
+        return Vec::new();
+    }
 
     let mut actions = Vec::new();
     if let Some(path) = selection::get_current_selected_path(model) {
@@ -15,8 +15,14 @@ pub fn view(model: &Model, frame: &mut Frame, rect: Rect) {
 
     let content = model.files.current.path.to_str().unwrap_or("");
     let style = Style::default().fg(Color::Gray);
-    let span = Span::styled(content, style);
-    let path = Line::from(span);
+    let mut spans = vec![Span::styled(content, style)];
+    if let Some(filter) = model.files.filters.get(&model.files.current.path) {
+        spans.push(Span::styled(
+            format!(" [filter: {}]", filter),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let path = Line::from(spans);
 
     let layout = Layout::default()
         .direction(Direction::Horizontal)
@@ -5,16 +5,24 @@ use crossterm::event::{self, KeyEvent, KeyEventKind};
 use crate::key::{Key, KeyCode, KeyModifier};
 
 pub fn from_keycode_string(keycodes: &str) -> VecDeque<Key> {
-    let mut keys = VecDeque::new();
+    get_keycodes(keycodes)
+        .into_iter()
+        .filter_map(Key::from_keycode_string)
+        .collect()
+}
 
-    let regex = regex::Regex::new(r"<[^>]*>|.").expect("Failed to compile regex");
-    for capture in regex.find_iter(keycodes).map(|m| m.as_str()) {
-        if let Some(key) = Key::from_keycode_string(capture) {
-            keys.push_back(key);
-        }
-    }
+pub fn try_from_keycode_string(keycodes: &str) -> Option<VecDeque<Key>> {
+    let keys: Option<VecDeque<_>> = get_keycodes(keycodes)
+        .into_iter()
+        .map(Key::from_keycode_string)
+        .collect();
+
+    keys.filter(|keys| !keys.is_empty())
+}
 
-    keys
+fn get_keycodes(keycodes: &str) -> Vec<&str> {
+    let regex = regex::Regex::new(r"<[^>]*>|.").expect("Failed to compile regex");
+    regex.find_iter(keycodes).map(|m| m.as_str()).collect()
 }
 
 pub fn to_key(event: &KeyEvent) -> Option<Key> {
@@ -36,7 +44,13 @@ pub fn to_key(event: &KeyEvent) -> Option<Key> {
         // event::KeyCode::PageUp => resolve(event.kind, KeyCode::),
         // event::KeyCode::PageDown => resolve(event.kind, KeyCode::),
         event::KeyCode::Tab => resolve(event.kind, KeyCode::Tab, modifier),
-        // event::KeyCode::BackTab => resolve(event.kind, KeyCode::),
+        event::KeyCode::BackTab => {
+            let mut modifier = modifier;
+            if !modifier.contains(&KeyModifier::Shift) {
+                modifier.push(KeyModifier::Shift);
+            }
+            resolve(event.kind, KeyCode::Tab, modifier)
+        }
         event::KeyCode::Delete => resolve(event.kind, KeyCode::Delete, modifier),
         // event::KeyCode::Insert => resolve(event.kind, KeyCode::),
         // event::KeyCode::F(_) => resolve(event.kind, KeyCode::),
@@ -145,4 +159,30 @@ mod tests {
         let keycodes = "<Invalid>";
         assert!(from_keycode_string(keycodes).is_empty());
     }
+
+    #[test]
+    fn from_keycode_string_uppercase_name() {
+        let keycodes = ":w<CR>";
+        let mut expected: VecDeque<Key> = VecDeque::new();
+        expected.push_back(Key {
+            code: KeyCode::from_char(':'),
+            modifiers: Vec::new(),
+        });
+        expected.push_back(Key {
+            code: KeyCode::from_char('w'),
+            modifiers: Vec::new(),
+        });
+        expected.push_back(Key {
+            code: KeyCode::Enter,
+            modifiers: Vec::new(),
+        });
+        assert_eq!(from_keycode_string(keycodes), expected);
+    }
+
+    #[test]
+    fn try_from_keycode_string_invalid() {
+        assert_eq!(try_from_keycode_string("a<Invalid>"), None);
+        assert_eq!(try_from_keycode_string(""), None);
+        assert_eq!(try_from_keycode_string("<C-w>").map(|k| k.len()), Some(1));
+    }
 }
@@ -165,7 +165,11 @@ pub enum KeyCode {
 
 impl KeyCode {
     pub fn from_keycode_string(keycode: &str) -> Option<Self> {
-        match keycode {
+        if keycode.chars().count() == 1 {
+            return keycode.chars().next().map(Self::from_char);
+        }
+
+        match keycode.to_ascii_lowercase().as_str() {
             "bslash" => Some(KeyCode::Backslash),
             "bs" => Some(KeyCode::Backspace),
             "bar" => Some(KeyCode::Bar),
@@ -188,13 +192,7 @@ impl KeyCode {
             "tab" => Some(KeyCode::Tab),
             "undo" => Some(KeyCode::Undo),
             "up" => Some(KeyCode::Up),
-            code => {
-                if code.len() == 1 {
-                    code.chars().next().map(Self::from_char)
-                } else {
-                    None
-                }
-            }
+            _ => None,
         }
     }
 
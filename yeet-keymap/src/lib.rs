@@ -13,12 +13,14 @@ use yeet_buffer::{
 mod buffer;
 pub mod conversion;
 pub mod key;
-mod map;
+pub mod map;
 pub mod message;
 mod tree;
 
 #[derive(Debug, thiserror::Error, PartialEq)]
-enum KeyMapError {
+pub enum KeyMapError {
+    #[error("Key sequence '{0}' is invalid.")]
+    InvalidKeySequence(String),
     #[error("Key sequence is incomplete.")]
     KeySequenceIncomplete,
     #[error("Failed to add mapping for mode {0}.")]
@@ -38,16 +40,20 @@ pub struct MessageResolver {
 
 impl Default for MessageResolver {
     fn default() -> Self {
+        Self::new(KeyMap::default())
+    }
+}
+
+impl MessageResolver {
+    pub fn new(keymap: KeyMap) -> Self {
         Self {
             buffer: KeyBuffer::default(),
             mode: Mode::default(),
             toggle: HashSet::new(),
-            tree: KeyMap::default().into_tree(),
+            tree: keymap.into_tree(),
         }
     }
-}
 
-impl MessageResolver {
     pub fn add_keys(
         &mut self,
         mut keys: VecDeque<Key>,
@@ -352,6 +358,6 @@ fn get_repeated_message(repeat: usize, msg: &KeymapMessage) -> Vec<KeymapMessage
 fn get_passthrough_by_mode(mode: &Mode) -> bool {
     match mode {
         Mode::Command(_) | Mode::Insert => true,
-        Mode::Navigation | Mode::Normal => false,
+        Mode::Navigation | Mode::Normal | Mode::Visual => false,
     }
 }
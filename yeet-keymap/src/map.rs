@@ -9,9 +9,11 @@ use yeet_buffer::{
 };
 
 use crate::{
+    conversion,
     key::{Key, KeyCode, KeyModifier},
     message::{Binding, BindingKind, KeymapMessage, NextBindingKind},
     tree::KeyTree,
+    KeyMapError,
 };
 
 pub struct KeyMap {
@@ -29,6 +31,35 @@ impl KeyMap {
         }
         tree
     }
+
+    pub fn remap(&mut self, mode: &Mode, keys: &str, target: &str) -> Result<(), KeyMapError> {
+        let keys: Vec<_> = match conversion::try_from_keycode_string(keys) {
+            Some(it) => it.into_iter().collect(),
+            None => return Err(KeyMapError::InvalidKeySequence(keys.to_owned())),
+        };
+
+        let target_keys: Vec<_> = match conversion::try_from_keycode_string(target) {
+            Some(it) => it.into_iter().collect(),
+            None => return Err(KeyMapError::InvalidKeySequence(target.to_owned())),
+        };
+
+        // NOTE: remapping to a bound sequence copies the binding, everything else is replayed
+        let mappings = self.mappings.entry(mode.clone()).or_default();
+        let binding = match mappings.iter().find(|(k, _)| k == &target_keys) {
+            Some((_, binding)) => binding.clone(),
+            None => Binding {
+                kind: BindingKind::Message(KeymapMessage::ExecuteKeySequence(target.to_owned())),
+                repeatable: false,
+                ..Default::default()
+            },
+        };
+
+        // NOTE: without timeouts, sequences sharing a prefix would shadow each other
+        mappings.retain(|(k, _)| !k.starts_with(&keys) && !keys.starts_with(k));
+        mappings.push((keys, binding));
+
+        Ok(())
+    }
 }
 
 impl Default for KeyMap {
@@ -39,6 +70,7 @@ impl Default for KeyMap {
             &mut mappings,
             vec![
                 Mode::Command(CommandMode::Command),
+                Mode::Command(CommandMode::Find),
                 Mode::Command(CommandMode::Search(SearchDirection::Up)),
                 Mode::Command(CommandMode::Search(SearchDirection::Down)),
             ],
@@ -94,6 +126,110 @@ impl Default for KeyMap {
             ],
         );
 
+        add_mapping(
+            &mut mappings,
+            vec![
+                Mode::Command(CommandMode::Command),
+                Mode::Command(CommandMode::Search(SearchDirection::Up)),
+                Mode::Command(CommandMode::Search(SearchDirection::Down)),
+            ],
+            vec![
+                (
+                    vec![Key::new(KeyCode::Up, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateCommandLineHistory(
+                            LineDirection::Up,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::Down, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateCommandLineHistory(
+                            LineDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Command(CommandMode::Find)],
+            vec![
+                (
+                    vec![Key::new(KeyCode::Up, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateFinder(
+                            LineDirection::Up,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::Down, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateFinder(
+                            LineDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('p'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateFinder(
+                            LineDirection::Up,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::NavigateFinder(
+                            LineDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('q'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::AddFinderMatchesToQuickFix),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Command(CommandMode::Command)],
+            vec![
+                (
+                    vec![Key::new(KeyCode::Tab, vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::CompleteCommandLine(
+                            LineDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::Tab, vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::CompleteCommandLine(
+                            LineDirection::Up,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
         add_mapping(
             &mut mappings,
             vec![Mode::Navigation],
@@ -116,6 +252,30 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('e'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ScrollPreview(
+                            LineDirection::Down,
+                        )),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('w'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::TogglePreviewFocus),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ScrollPreview(LineDirection::Up)),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('"'), vec![]),
@@ -153,6 +313,16 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('f'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ExecuteKeySequence(
+                            ":filter ".to_owned(),
+                        )),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('g'), vec![]),
@@ -163,6 +333,18 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![
+                        Key::new(KeyCode::from_char('z'), vec![]),
+                        Key::new(KeyCode::from_char('h'), vec![]),
+                    ],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ExecuteCommandString(
+                            "set hidden!".to_owned(),
+                        )),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![Key::new(KeyCode::from_char('h'), vec![])],
                     Binding {
@@ -194,6 +376,16 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('u'), vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::ExecuteCommandString(
+                            "undo-ops".to_owned(),
+                        )),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('y'), vec![]),
@@ -222,260 +414,293 @@ impl Default for KeyMap {
             vec![Mode::Navigation, Mode::Normal],
             vec![
                 (
-                    vec![Key::new(KeyCode::Space, vec![])],
+                    vec![Key::new(KeyCode::from_char('@'), vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::ToggleQuickFix),
-                        repeatable: false,
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::ReplayMacro(' ')),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::Esc, vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('@'), vec![]),
+                        Key::new(KeyCode::from_char('@'), vec![]),
+                    ],
                     Binding {
-                        force: Some(Mode::Navigation),
+                        kind: BindingKind::Message(KeymapMessage::ReplayMacro('@')),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('1'), vec![])],
+                    vec![Key::new(KeyCode::from_char('\''), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(1),
+                        expects: Some(NextBindingKind::Raw(None)),
+                        kind: BindingKind::Message(KeymapMessage::NavigateToMark(' ')),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('2'), vec![])],
+                    vec![Key::new(KeyCode::from_char('a'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(2),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Motion(CursorDirection::LineEnd),
+                        repeat: None,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('3'), vec![])],
+                    vec![Key::new(KeyCode::from_char('i'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(3),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Motion(CursorDirection::LineStart),
+                        repeat: None,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('4'), vec![])],
+                    vec![Key::new(KeyCode::from_char('m'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(4),
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::SetMark(' ')),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('5'), vec![])],
+                    vec![Key::new(KeyCode::from_char('o'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(5),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Modification(TextModification::InsertNewLine(
+                            LineDirection::Down,
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('6'), vec![])],
+                    vec![Key::new(KeyCode::from_char('o'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(6),
+                        force: Some(Mode::Insert),
+                        kind: BindingKind::Modification(TextModification::InsertNewLine(
+                            LineDirection::Up,
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('7'), vec![])],
+                    vec![Key::new(KeyCode::from_char('q'), vec![])],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(7),
+                        expects: Some(NextBindingKind::Raw(Some(
+                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
+                        ))),
+                        kind: BindingKind::Message(KeymapMessage::StartMacro(' ')),
+                        repeatable: false,
+                        toggle: Some((
+                            "macro-toggle".to_owned(),
+                            BindingKind::Message(KeymapMessage::StopMacro),
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('8'), vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('q'), vec![]),
+                        Key::new(KeyCode::from_char(':'), vec![]),
+                    ],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(8),
+                        kind: BindingKind::Message(KeymapMessage::OpenCommandLineHistory(
+                            CommandMode::Command,
+                        )),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('9'), vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('q'), vec![]),
+                        Key::new(KeyCode::from_char('/'), vec![]),
+                    ],
                     Binding {
-                        kind: BindingKind::Repeat,
-                        repeat: Some(9),
+                        kind: BindingKind::Message(KeymapMessage::OpenCommandLineHistory(
+                            CommandMode::Search(SearchDirection::Down),
+                        )),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('@'), vec![])],
+                    vec![Key::new(KeyCode::from_char('/'), vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::ReplayMacro(' ')),
+                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Down))),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![
-                        Key::new(KeyCode::from_char('@'), vec![]),
-                        Key::new(KeyCode::from_char('@'), vec![]),
-                    ],
+                    vec![Key::new(KeyCode::from_char('?'), vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::ReplayMacro('@')),
+                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Up))),
+                        repeatable: false,
                         ..Default::default()
                     },
                 ),
+            ],
+        );
+
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Navigation, Mode::Normal, Mode::Visual],
+            vec![
                 (
-                    vec![Key::new(KeyCode::from_char('\''), vec![])],
+                    vec![Key::new(KeyCode::Space, vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(None)),
-                        kind: BindingKind::Message(KeymapMessage::NavigateToMark(' ')),
+                        kind: BindingKind::Message(KeymapMessage::ToggleQuickFix),
                         repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char(':'), vec![])],
+                    vec![Key::new(KeyCode::Esc, vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Command)),
-                        repeatable: false,
+                        force: Some(Mode::Navigation),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('/'), vec![])],
+                    vec![Key::new(KeyCode::from_char('1'), vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Down))),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(1),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('?'), vec![])],
+                    vec![Key::new(KeyCode::from_char('2'), vec![])],
                     Binding {
-                        force: Some(Mode::Command(CommandMode::Search(SearchDirection::Up))),
-                        repeatable: false,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(2),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('a'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('3'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Motion(CursorDirection::LineEnd),
-                        repeat: None,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(3),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('d'), vec![KeyModifier::Ctrl])],
+                    vec![Key::new(KeyCode::from_char('4'), vec![])],
                     Binding {
-                        kind: BindingKind::Message(KeymapMessage::Buffer(
-                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown),
-                        )),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(4),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('5'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Bottom),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(5),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![
-                        Key::new(KeyCode::from_char('g'), vec![]),
-                        Key::new(KeyCode::from_char('g'), vec![]),
-                    ],
+                    vec![Key::new(KeyCode::from_char('6'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Top),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(6),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('i'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('7'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Motion(CursorDirection::LineStart),
-                        repeat: None,
+                        kind: BindingKind::Repeat,
+                        repeat: Some(7),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('j'), vec![])],
+                    vec![Key::new(KeyCode::from_char('8'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Down),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(8),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('k'), vec![])],
+                    vec![Key::new(KeyCode::from_char('9'), vec![])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Up),
+                        kind: BindingKind::Repeat,
+                        repeat: Some(9),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('m'), vec![])],
+                    vec![Key::new(KeyCode::from_char(':'), vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::SetMark(' ')),
+                        force: Some(Mode::Command(CommandMode::Command)),
                         repeatable: false,
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('n'), vec![])],
+                    vec![Key::new(KeyCode::from_char('d'), vec![KeyModifier::Ctrl])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Search(Search::Next)),
+                        kind: BindingKind::Message(KeymapMessage::Buffer(
+                            BufferMessage::MoveViewPort(ViewPortDirection::HalfPageDown),
+                        )),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('g'), vec![KeyModifier::Shift])],
                     Binding {
-                        kind: BindingKind::Motion(CursorDirection::Search(Search::Previous)),
+                        kind: BindingKind::Motion(CursorDirection::Bottom),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('o'), vec![])],
+                    vec![
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                        Key::new(KeyCode::from_char('g'), vec![]),
+                    ],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Modification(TextModification::InsertNewLine(
-                            LineDirection::Down,
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Top),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('o'), vec![KeyModifier::Shift])],
+                    vec![Key::new(KeyCode::from_char('j'), vec![])],
                     Binding {
-                        force: Some(Mode::Insert),
-                        kind: BindingKind::Modification(TextModification::InsertNewLine(
-                            LineDirection::Up,
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Down),
                         ..Default::default()
                     },
                 ),
                 (
-                    vec![Key::new(KeyCode::from_char('q'), vec![])],
+                    vec![Key::new(KeyCode::from_char('k'), vec![])],
                     Binding {
-                        expects: Some(NextBindingKind::Raw(Some(
-                            Regex::new("[[:alpha:]]").expect("Invalid regex"),
-                        ))),
-                        kind: BindingKind::Message(KeymapMessage::StartMacro(' ')),
-                        repeatable: false,
-                        toggle: Some((
-                            "macro-toggle".to_owned(),
-                            BindingKind::Message(KeymapMessage::StopMacro),
-                        )),
+                        kind: BindingKind::Motion(CursorDirection::Up),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('n'), vec![])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Next)),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('n'), vec![KeyModifier::Shift])],
+                    Binding {
+                        kind: BindingKind::Motion(CursorDirection::Search(Search::Previous)),
                         ..Default::default()
                     },
                 ),
@@ -684,6 +909,20 @@ impl Default for KeyMap {
                         ..Default::default()
                     },
                 ),
+                (
+                    vec![Key::new(KeyCode::from_char('u'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(BufferMessage::Undo)),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('r'), vec![KeyModifier::Ctrl])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::Buffer(BufferMessage::Redo)),
+                        ..Default::default()
+                    },
+                ),
                 (
                     vec![
                         Key::new(KeyCode::from_char('g'), vec![]),
@@ -818,6 +1057,50 @@ impl Default for KeyMap {
             ],
         );
 
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Navigation, Mode::Normal],
+            vec![(
+                vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                Binding {
+                    force: Some(Mode::Visual),
+                    repeatable: false,
+                    ..Default::default()
+                },
+            )],
+        );
+
+        add_mapping(
+            &mut mappings,
+            vec![Mode::Visual],
+            vec![
+                (
+                    vec![Key::new(KeyCode::from_char('d'), vec![])],
+                    Binding {
+                        kind: BindingKind::Modification(TextModification::DeleteLine),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift])],
+                    Binding {
+                        force: Some(Mode::Navigation),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    vec![Key::new(KeyCode::from_char('y'), vec![])],
+                    Binding {
+                        kind: BindingKind::Message(KeymapMessage::YankToJunkYard(0)),
+                        repeatable: false,
+                        ..Default::default()
+                    },
+                ),
+            ],
+        );
+
         Self { mappings }
     }
 }
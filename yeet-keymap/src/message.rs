@@ -2,8 +2,8 @@ use std::path::PathBuf;
 
 use regex::Regex;
 use yeet_buffer::{
-    message::{BufferMessage, CursorDirection, TextModification},
-    model::Mode,
+    message::{BufferMessage, CursorDirection, LineDirection, TextModification},
+    model::{CommandMode, Mode},
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -88,26 +88,33 @@ impl KeySequence {
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum KeymapMessage {
+    AddFinderMatchesToQuickFix,
     Buffer(BufferMessage),
     ClearSearchHighlight,
+    CompleteCommandLine(LineDirection),
     DeleteMarks(Vec<char>),
     ExecuteCommand,
     ExecuteCommandString(String),
     ExecuteKeySequence(String),
     ExecuteRegister(char),
     LeaveCommandMode,
+    NavigateCommandLineHistory(LineDirection),
+    NavigateFinder(LineDirection),
     NavigateToMark(char),
     NavigateToParent,
     NavigateToPath(PathBuf),
     NavigateToPathAsPreview(PathBuf),
     NavigateToSelected,
+    OpenCommandLineHistory(CommandMode),
     OpenSelected,
     PasteFromJunkYard(char),
     Print(Vec<PrintContent>),
     ReplayMacro(char),
+    ScrollPreview(LineDirection),
     SetMark(char),
     StartMacro(char),
     StopMacro,
+    TogglePreviewFocus,
     ToggleQuickFix,
     Quit(QuitMode),
     YankPathToClipboard,
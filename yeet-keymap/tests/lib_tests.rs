@@ -1,9 +1,10 @@
 use yeet_buffer::{
-    message::{BufferMessage, CursorDirection, TextModification},
+    message::{BufferMessage, CursorDirection, LineDirection, TextModification},
     model::{CommandMode, Mode},
 };
 use yeet_keymap::{
     key::{Key, KeyCode, KeyModifier},
+    map::KeyMap,
     message::{KeySequence, KeymapMessage},
     MessageResolver,
 };
@@ -157,6 +158,43 @@ fn add_and_resolve_key_normal_d0() {
     assert_eq!(1, result.0.len());
 }
 
+#[test]
+fn add_and_resolve_key_normal_2u() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Normal;
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('2'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('u'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        vec![
+            KeymapMessage::Buffer(BufferMessage::Undo),
+            KeymapMessage::Buffer(BufferMessage::Undo)
+        ],
+        result.0
+    );
+    assert_eq!(KeySequence::Completed("2u".to_string()), result.1);
+}
+
+#[test]
+fn add_and_resolve_key_normal_ctrl_r() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Normal;
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('r'), vec![KeyModifier::Ctrl]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::Redo)),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("<C-r>".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
 #[test]
 fn add_and_resolve_key_command_q() {
     let mut resolver = MessageResolver::default();
@@ -360,3 +398,206 @@ fn add_and_resolve_key_normal_qa_and_q() {
     assert_eq!(KeySequence::Completed("q".to_string()), result.1);
     assert_eq!(1, result.0.len());
 }
+
+#[test]
+fn add_and_resolve_key_remapped_to_binding() {
+    let mut keymap = KeyMap::default();
+    keymap.remap(&Mode::Normal, "<C-j>", "j").unwrap();
+
+    let mut resolver = MessageResolver::new(keymap);
+    resolver.mode = Mode::Normal;
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('3'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('j'), vec![KeyModifier::Ctrl]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            3,
+            CursorDirection::Down
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("3<C-j>".to_string()), result.1);
+}
+
+#[test]
+fn add_and_resolve_key_remapped_to_sequence() {
+    let mut keymap = KeyMap::default();
+    keymap.remap(&Mode::Navigation, "d", ":d!<CR>").unwrap();
+
+    let mut resolver = MessageResolver::new(keymap);
+    let result = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        vec![KeymapMessage::ExecuteKeySequence(":d!<CR>".to_string())],
+        result.0
+    );
+    assert_eq!(KeySequence::Completed("d".to_string()), result.1);
+}
+
+#[test]
+fn remap_with_invalid_keys() {
+    let mut keymap = KeyMap::default();
+    assert!(keymap.remap(&Mode::Normal, "<Invalid>", "j").is_err());
+    assert!(keymap.remap(&Mode::Normal, "j", "").is_err());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_shift_v() {
+    let mut resolver = MessageResolver::default();
+    let result = resolver.add_key(Key::new(KeyCode::from_char('v'), vec![KeyModifier::Shift]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::ChangeMode(
+            Mode::Navigation,
+            Mode::Visual
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("V".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_d() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('d'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::Modification(
+            1,
+            TextModification::DeleteLine
+        ))),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("d".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_visual_j() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Visual;
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('j'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::Buffer(BufferMessage::MoveCursor(
+            1,
+            CursorDirection::Down
+        ))),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_f() {
+    let mut resolver = MessageResolver::default();
+    let result = resolver.add_key(Key::new(KeyCode::from_char('f'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::ExecuteKeySequence(":filter ".to_string())),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("f".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_q_colon() {
+    let mut resolver = MessageResolver::default();
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('q'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char(':'), vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::OpenCommandLineHistory(CommandMode::Command)),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("q:".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_command_up() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Command(CommandMode::Command);
+
+    let result = resolver.add_key(Key::new(KeyCode::Up, vec![]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::NavigateCommandLineHistory(
+            LineDirection::Up
+        )),
+        result.0.first()
+    );
+    assert_eq!(KeySequence::Completed("<up>".to_string()), result.1);
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_command_shift_tab() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Command(CommandMode::Command);
+
+    let result = resolver.add_key(Key::new(KeyCode::Tab, vec![KeyModifier::Shift]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::CompleteCommandLine(LineDirection::Up)),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_find_ctrl_n() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Command(CommandMode::Find);
+
+    let result = resolver.add_key(Key::new(KeyCode::from_char('n'), vec![KeyModifier::Ctrl]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::NavigateFinder(LineDirection::Down)),
+        result.0.first()
+    );
+    assert_eq!(1, result.0.len());
+}
+
+#[test]
+fn add_and_resolve_key_navigation_count_ctrl_e() {
+    let mut resolver = MessageResolver::default();
+    resolver.mode = Mode::Navigation;
+
+    let _ = resolver.add_key(Key::new(KeyCode::from_char('3'), vec![]));
+    let result = resolver.add_key(Key::new(KeyCode::from_char('e'), vec![KeyModifier::Ctrl]));
+
+    println!("{:?}", result);
+
+    assert_eq!(
+        Some(&KeymapMessage::ScrollPreview(LineDirection::Down)),
+        result.0.first()
+    );
+    assert_eq!(3, result.0.len());
+}
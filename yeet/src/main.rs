@@ -55,6 +55,11 @@ fn cli() -> Command {
                 .value_parser(value_parser!(PathBuf))
                 .help("path to open in yeet on startup"),
             // NOTE: options
+            Arg::new("config")
+                .long("config")
+                .action(ArgAction::Set)
+                .value_parser(value_parser!(PathBuf))
+                .help("path to the config file, defaults to <config dir>/yeet/config.toml"),
             Arg::new("selection-to-file-on-open")
                 .long("selection-to-file-on-open")
                 .action(ArgAction::Set)
@@ -103,6 +108,7 @@ fn get_logging_path() -> Result<String, Error> {
 
 fn get_settings(args: &ArgMatches) -> Settings {
     Settings {
+        config_path: args.get_one("config").cloned(),
         selection_to_file_on_open: args.get_one("selection-to-file-on-open").cloned(),
         selection_to_stdout_on_open: args.get_flag("selection-to-stdout-on-open"),
         startup_path: args.get_one("path").cloned(),